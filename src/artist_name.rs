@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use musicbrainz_rs::entity::artist_credit::ArtistCredit;
+use serde_json::Value;
+
+/// An artist alias along with its locale, which `musicbrainz_rs` does not deserialize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtistAlias {
+    pub artist_id: String,
+    pub name: String,
+    pub sort_name: String,
+    pub locale: String,
+    pub primary: bool,
+}
+
+/// Collects the aliases with a locale of every credited artist in a raw MusicBrainz response.
+pub fn collect_artist_aliases(json: &Value, aliases: &mut Vec<ArtistAlias>) {
+    match json {
+        Value::Object(object) => {
+            for credit in object.get("artist-credit").and_then(Value::as_array).into_iter().flatten() {
+                let artist = &credit["artist"];
+                for alias in artist["aliases"].as_array().into_iter().flatten() {
+                    let (Some(artist_id), Some(name), Some(locale)) =
+                        (artist["id"].as_str(), alias["name"].as_str(), alias["locale"].as_str())
+                    else {
+                        continue;
+                    };
+                    let alias = ArtistAlias {
+                        artist_id: artist_id.to_string(),
+                        name: name.to_string(),
+                        sort_name: alias["sort-name"].as_str().unwrap_or(name).to_string(),
+                        locale: locale.to_string(),
+                        primary: alias["primary"].as_bool().unwrap_or_default(),
+                    };
+                    if !aliases.contains(&alias) {
+                        aliases.push(alias);
+                    }
+                }
+            }
+            for value in object.values() {
+                collect_artist_aliases(value, aliases);
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                collect_artist_aliases(value, aliases);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Which name of a credited artist is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtistName {
+    /// The name as credited on the release or track, e.g. "Prince and the Revolution".
+    Credited,
+    /// The name of the artist entity, e.g. "Prince".
+    Canonical,
+    /// The sort name of the artist entity, e.g. "Beatles, The".
+    Sort,
+    /// The alias for a locale such as "en" or "ja_JP", preferring primary aliases and falling back to the credited
+    /// name. A locale without a country also matches the aliases for its countries.
+    Alias(String),
+}
+
+impl FromStr for ArtistName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "credited" => Ok(Self::Credited),
+            "canonical" => Ok(Self::Canonical),
+            "sort" => Ok(Self::Sort),
+            _ => match s.strip_prefix("alias:") {
+                Some(locale) if !locale.is_empty() => Ok(Self::Alias(locale.to_string())),
+                _ => Err(format!("invalid artist name \"{s}\", expected one of credited, canonical, sort, alias:<locale>")),
+            },
+        }
+    }
+}
+
+fn matches_locale(alias_locale: &str, locale: &str) -> bool {
+    alias_locale == locale || alias_locale.split('_').next() == Some(locale)
+}
+
+impl ArtistName {
+    fn find_alias<'a>(locale: &str, credit: &ArtistCredit, aliases: &'a [ArtistAlias]) -> Option<&'a ArtistAlias> {
+        let mut candidates = aliases.iter().filter(|a| a.artist_id == credit.artist.id && matches_locale(&a.locale, locale));
+        let first = candidates.next()?;
+        Some(if first.primary { first } else { candidates.find(|a| a.primary).unwrap_or(first) })
+    }
+
+    /// The name of a single credited artist.
+    pub fn resolve(&self, credit: &ArtistCredit, aliases: &[ArtistAlias]) -> String {
+        match self {
+            Self::Credited => credit.name.clone(),
+            Self::Canonical => credit.artist.name.clone(),
+            Self::Sort => credit.artist.sort_name.clone(),
+            Self::Alias(locale) => Self::find_alias(locale, credit, aliases).map_or_else(|| credit.name.clone(), |a| a.name.clone()),
+        }
+    }
+
+    /// The sort name of a single credited artist, from its alias if names are resolved through aliases.
+    pub fn resolve_sort(&self, credit: &ArtistCredit, aliases: &[ArtistAlias]) -> String {
+        match self {
+            Self::Alias(locale) => {
+                Self::find_alias(locale, credit, aliases).map_or_else(|| credit.artist.sort_name.clone(), |a| a.sort_name.clone())
+            }
+            _ => credit.artist.sort_name.clone(),
+        }
+    }
+
+    /// The names of all credited artists joined by their join phrases, e.g. "Simon & Garfunkel".
+    pub fn join(&self, credits: &[ArtistCredit], aliases: &[ArtistAlias]) -> String {
+        credits.iter().map(|c| format!("{}{}", self.resolve(c, aliases), c.joinphrase.as_deref().unwrap_or_default())).collect()
+    }
+
+    /// The sort names of all credited artists joined by their join phrases.
+    pub fn join_sort(&self, credits: &[ArtistCredit], aliases: &[ArtistAlias]) -> String {
+        credits.iter().map(|c| format!("{}{}", self.resolve_sort(c, aliases), c.joinphrase.as_deref().unwrap_or_default())).collect()
+    }
+}
@@ -0,0 +1,18 @@
+/// Returns the barcode as a 13-digit EAN if it is a valid EAN-13 or UPC-A (which gets a leading zero), i.e. the form
+/// the CATALOG command expects.
+pub fn normalize_barcode(barcode: &str) -> Option<String> {
+    let ean = match barcode.len() {
+        12 => format!("0{barcode}"),
+        13 => barcode.to_string(),
+        _ => return None,
+    };
+    let digits = ean.chars().map(|c| c.to_digit(10)).collect::<Option<Vec<_>>>()?;
+
+    // digits are weighted 1, 3, 1, 3, ... from the left, and the check digit brings the sum to a multiple of 10
+    let sum = digits[..12].iter().enumerate().map(|(i, d)| if i % 2 == 0 { *d } else { d * 3 }).sum::<u32>();
+    if (10 - sum % 10) % 10 == digits[12] {
+        Some(ean)
+    } else {
+        None
+    }
+}
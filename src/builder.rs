@@ -0,0 +1,684 @@
+use std::str::FromStr;
+
+use if_chain::if_chain;
+use musicbrainz_rs::entity::artist_credit::ArtistCredit;
+use musicbrainz_rs::entity::recording::Recording;
+use musicbrainz_rs::entity::date_string::DateString;
+use musicbrainz_rs::entity::release::{Media, Release, ReleaseStatus, Track};
+
+use crate::artist_name::{ArtistAlias, ArtistName};
+use crate::barcode::normalize_barcode;
+use crate::credits::{
+    parent_work, recording_works, related_artists, soloists, split_movement_number, work_artists, COMPOSER_RELATIONS, LYRICIST_RELATIONS,
+};
+use crate::cuesheet::{CueFile, CueIndex, CueSheet, CueTime, CueTrack, FileType, Rem};
+use crate::date::PartialDate;
+use crate::discid::DiscIds;
+use crate::genre::GenreOptions;
+use crate::isrc::normalize_isrc;
+use crate::layout::{split_image, Layout};
+use crate::pseudo_release::Script;
+use crate::sanitize::sanitize_file_name;
+use crate::template::{Template, TemplateValue};
+use crate::toc::Toc;
+
+pub const DEFAULT_FILE_TEMPLATE: &str = "CDImage.flac";
+pub const DEFAULT_TRACK_FILE_TEMPLATE: &str = "{track:02} - {title}.flac";
+pub const DEFAULT_OUTPUT_TEMPLATE: &str = "{format} {medium:02}";
+pub const DEFAULT_CREDIT_SEPARATOR: &str = "; ";
+pub const INDEX_SOURCE_REM: &str = "INDEX_SOURCE";
+
+/// MusicBrainz ID of the special purpose artist "Various Artists", credited on compilations.
+pub const VARIOUS_ARTISTS_ID: &str = "89ad4ac3-39f7-470e-963a-56509c546377";
+
+/// Placeholders available in the FILE name template and the cuesheet name template.
+pub const FILE_TEMPLATE_FIELDS: &[&str] = &["artist", "album", "medium", "medium_title", "format", "discs", "catalog"];
+
+/// Placeholders available in the per-track FILE name template.
+pub const TRACK_FILE_TEMPLATE_FIELDS: &[&str] =
+    &["artist", "album", "medium", "medium_title", "format", "discs", "catalog", "track", "title", "performer"];
+
+/// MusicBrainz IDs of the credited artists, separated like multi-value tags written by Picard.
+pub fn join_artist_ids(artists: &[ArtistCredit]) -> String {
+    artists.iter().map(|a| a.artist.id.as_str()).collect::<Vec<_>>().join("; ")
+}
+
+/// Whether the credit names "Various Artists", i.e. the release is a compilation.
+pub fn is_various_artists(artists: &[ArtistCredit]) -> bool {
+    artists.iter().any(|a| a.artist.id == VARIOUS_ARTISTS_ID)
+}
+
+/// Where the INDEX 01 positions of a generated cuesheet come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexSource {
+    /// Sector offsets of a disc ID attached to the medium, exact to the frame.
+    Toc,
+    /// Sum of the track lengths, which are rounded to milliseconds.
+    TrackLengths,
+}
+
+impl IndexSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Toc => "TOC",
+            Self::TrackLengths => "TRACK_LENGTHS",
+        }
+    }
+}
+
+/// Returns an error naming the first two media whose cuesheets would be written to the same file. Names are
+/// compared case-insensitively, as they are on Windows and macOS.
+pub fn check_name_collisions(media: &[MediumCueSheet]) -> Result<(), String> {
+    for (i, medium) in media.iter().enumerate() {
+        if let Some(other) = media[..i].iter().find(|m| m.name.to_lowercase() == medium.name.to_lowercase()) {
+            return Err(format!("media {} and {} would both be written to \"{}.cue\"", other.position, medium.position, medium.name));
+        }
+    }
+    Ok(())
+}
+
+/// Which dates are written as REM DATE and REM ORIGINALDATE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateSource {
+    /// REM DATE holds the date of the release itself, e.g. of a remaster.
+    Release,
+    /// REM DATE holds the first release date of the release group, i.e. of the original release.
+    ReleaseGroup,
+    /// REM DATE holds the date of the release and REM ORIGINALDATE the first release date of the release group.
+    Both,
+}
+
+impl FromStr for DateSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "release" => Ok(Self::Release),
+            "release-group" => Ok(Self::ReleaseGroup),
+            "both" => Ok(Self::Both),
+            _ => Err(format!("invalid date source \"{s}\", expected one of release, release-group, both")),
+        }
+    }
+}
+
+/// Formats a MusicBrainz date with the precision it has, e.g. "1969" or "1969-09-26", or `None` if it is not a date.
+fn format_date(date: &DateString) -> Option<String> {
+    date.0.parse::<PartialDate>().ok().map(|d| d.to_string())
+}
+
+/// Status of the release as written by Picard.
+fn release_status_name(status: &ReleaseStatus) -> Option<&'static str> {
+    match status {
+        ReleaseStatus::Official => Some("official"),
+        ReleaseStatus::Promotion => Some("promotion"),
+        ReleaseStatus::Bootleg => Some("bootleg"),
+        ReleaseStatus::PseudoRelease => Some("pseudo-release"),
+        _ => None,
+    }
+}
+
+/// Whether a catalog number of a release label is an actual number, MusicBrainz uses "[none]" for releases without one.
+fn is_catalog_number(catalog_number: &str) -> bool {
+    !catalog_number.is_empty() && catalog_number != "[none]"
+}
+
+/// A generated cuesheet along with the file name it should be saved under, without the ".cue" extension.
+pub struct MediumCueSheet {
+    pub name: String,
+    pub position: u32,
+    pub cue_sheet: CueSheet,
+}
+
+/// Turns a MusicBrainz release into one cuesheet per medium.
+pub struct CueSheetBuilder<'a> {
+    release: &'a Release,
+    medium: Option<u32>,
+    toc: Option<Toc>,
+    file_template: Template,
+    file_type: FileType,
+    layout: Layout,
+    track_file_template: Template,
+    output_template: Template,
+    credit_separator: String,
+    classical: bool,
+    title_suffix: bool,
+    date_source: DateSource,
+    comment: Option<String>,
+    genres: GenreOptions,
+    artist_name: ArtistName,
+    artist_aliases: &'a [ArtistAlias],
+    omit_album_performer: bool,
+    recording_artists: bool,
+    pseudo_release: Option<&'a Release>,
+    script: Script,
+}
+
+impl<'a> CueSheetBuilder<'a> {
+    pub fn new(release: &'a Release) -> Self {
+        Self {
+            release,
+            medium: None,
+            toc: None,
+            file_template: DEFAULT_FILE_TEMPLATE.parse().unwrap(),
+            file_type: FileType::Wave,
+            layout: Layout::Image,
+            track_file_template: DEFAULT_TRACK_FILE_TEMPLATE.parse().unwrap(),
+            output_template: DEFAULT_OUTPUT_TEMPLATE.parse().unwrap(),
+            credit_separator: DEFAULT_CREDIT_SEPARATOR.to_string(),
+            classical: false,
+            title_suffix: true,
+            date_source: DateSource::ReleaseGroup,
+            comment: None,
+            genres: GenreOptions::default(),
+            artist_name: ArtistName::Credited,
+            artist_aliases: &[],
+            omit_album_performer: false,
+            recording_artists: false,
+            pseudo_release: None,
+            script: Script::Original,
+        }
+    }
+
+    /// Only build the cuesheet of the medium at this position.
+    pub fn medium(mut self, position: u32) -> Self {
+        self.medium = Some(position);
+        self
+    }
+
+    /// Use this TOC for the INDEX positions instead of the disc IDs attached to the medium.
+    pub fn toc(mut self, toc: Toc) -> Self {
+        self.toc = Some(toc);
+        self
+    }
+
+    /// Name the audio FILE after this template, see [`FILE_TEMPLATE_FIELDS`] for the placeholders.
+    pub fn file_template(mut self, template: Template) -> Self {
+        self.file_template = template;
+        self
+    }
+
+    pub fn file_type(mut self, file_type: FileType) -> Self {
+        self.file_type = file_type;
+        self
+    }
+
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Name the per-track FILEs of the track layouts after this template, see [`TRACK_FILE_TEMPLATE_FIELDS`] for
+    /// the placeholders.
+    pub fn track_file_template(mut self, template: Template) -> Self {
+        self.track_file_template = template;
+        self
+    }
+
+    /// Name the cuesheets after this template, see [`FILE_TEMPLATE_FIELDS`] for the placeholders.
+    pub fn output_template(mut self, template: Template) -> Self {
+        self.output_template = template;
+        self
+    }
+
+    /// Join multiple SONGWRITER, COMPOSER and LYRICIST credits with this separator.
+    pub fn credit_separator(mut self, separator: impl Into<String>) -> Self {
+        self.credit_separator = separator.into();
+        self
+    }
+
+    /// Describe tracks by their work and movement, conductor, orchestra and soloists, and use the composers as album
+    /// PERFORMER, falling back to the release artist if the works have no composer.
+    pub fn classical(mut self, classical: bool) -> Self {
+        self.classical = classical;
+        self
+    }
+
+    /// Append the medium format, position and title to the TITLE of multi-disc releases, as in
+    /// "Abbey Road- CD 01: Side One". Without it, the medium is only described by the DISCNUMBER and DISCSUBTITLE REMs.
+    pub fn title_suffix(mut self, title_suffix: bool) -> Self {
+        self.title_suffix = title_suffix;
+        self
+    }
+
+    pub fn date_source(mut self, date_source: DateSource) -> Self {
+        self.date_source = date_source;
+        self
+    }
+
+    /// Write this text as REM COMMENT.
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    pub fn genres(mut self, genres: GenreOptions) -> Self {
+        self.genres = genres;
+        self
+    }
+
+    pub fn artist_name(mut self, artist_name: ArtistName) -> Self {
+        self.artist_name = artist_name;
+        self
+    }
+
+    /// Aliases of the credited artists, for [`ArtistName::Alias`].
+    pub fn artist_aliases(mut self, aliases: &'a [ArtistAlias]) -> Self {
+        self.artist_aliases = aliases;
+        self
+    }
+
+    /// Only write a track PERFORMER if it differs from the release artist. Compilations credited to "Various
+    /// Artists" always get one.
+    pub fn omit_album_performer(mut self, omit_album_performer: bool) -> Self {
+        self.omit_album_performer = omit_album_performer;
+        self
+    }
+
+    /// Credit tracks with the artists of their recordings instead of the artists printed on the release, which can
+    /// differ, e.g. by a "feat." on the sleeve. Tracks without artists of their own always use the recording's.
+    pub fn recording_artists(mut self, recording_artists: bool) -> Self {
+        self.recording_artists = recording_artists;
+        self
+    }
+
+    /// Pseudo-release with the Latin-script tracklist of the release, whose titles are written according to
+    /// [`Self::script`]. Timings and IDs always come from the release.
+    pub fn pseudo_release(mut self, pseudo_release: &'a Release) -> Self {
+        self.pseudo_release = Some(pseudo_release);
+        self
+    }
+
+    pub fn script(mut self, script: Script) -> Self {
+        self.script = script;
+        self
+    }
+
+    /// The title to write and the one for REM TITLE_LATIN, given the title in the release and in the pseudo-release.
+    fn titles(&self, original: &str, latin: Option<&str>) -> (String, Option<String>) {
+        match (self.script, latin) {
+            (Script::Latin, Some(latin)) => (latin.to_string(), None),
+            (Script::Both, Some(latin)) if latin != original => (original.to_string(), Some(latin.to_string())),
+            _ => (original.to_string(), None),
+        }
+    }
+
+    fn album_titles(&self) -> (String, Option<String>) {
+        self.titles(&self.release.title, self.pseudo_release.map(|r| r.title.as_str()))
+    }
+
+    /// The medium at the same position in the pseudo-release.
+    fn pseudo_medium(&self, medium: &Media) -> Option<&'a Media> {
+        self.pseudo_release?.media.iter().flatten().find(|m| m.position == medium.position)
+    }
+
+    fn medium_title(&self, medium: &Media) -> Option<String> {
+        let latin = self.pseudo_medium(medium).and_then(|m| m.title.as_deref()).filter(|t| !t.is_empty());
+        medium.title.as_deref().map(|t| self.titles(t, latin).0)
+    }
+
+    fn track_artists<'t>(&self, track: &'t Track) -> Option<&'t [ArtistCredit]> {
+        let recording_artists = track.recording.as_ref().and_then(|r| r.artist_credit.as_deref());
+        match track.artist_credit.as_deref() {
+            Some(artists) if !artists.is_empty() && !self.recording_artists => Some(artists),
+            _ => recording_artists,
+        }
+    }
+
+    fn is_compilation(&self) -> bool {
+        self.release.artist_credit.as_deref().is_some_and(is_various_artists)
+    }
+
+    fn join_artists(&self, credits: &[ArtistCredit]) -> String {
+        self.artist_name.join(credits, self.artist_aliases)
+    }
+
+    /// Template values describing the release and the given medium.
+    fn medium_values(&self, medium: &Media) -> Vec<(&'static str, TemplateValue)> {
+        let catalog = self.release.label_info.iter().flatten().filter_map(|l| l.catalog_number.clone()).find(|c| is_catalog_number(c));
+
+        vec![
+            ("artist", self.release.artist_credit.as_deref().map(|a| self.join_artists(a)).unwrap_or_default().into()),
+            ("album", self.album_titles().0.into()),
+            ("medium", medium.position.unwrap_or_default().into()),
+            ("medium_title", self.medium_title(medium).unwrap_or_default().into()),
+            ("format", medium.format.clone().unwrap_or_default().into()),
+            ("discs", (self.release.media.iter().flatten().count() as u32).into()),
+            ("catalog", catalog.unwrap_or_default().into()),
+        ]
+    }
+
+    pub fn build(&self) -> Vec<MediumCueSheet> {
+        let release_rems = self.release_rems();
+        let media = self.release.media.as_deref().unwrap_or_default();
+        let is_album = media.len() > 1;
+
+        media
+            .iter()
+            .filter(|medium| self.medium.is_none() || medium.position == self.medium)
+            .map(|medium| {
+                let position = medium.position.unwrap_or_default();
+
+                let mut title = self.album_titles().0;
+                if is_album && self.title_suffix {
+                    title += &format!("- {} {position:02}", medium.format.clone().unwrap_or_default());
+                }
+
+                let (tracks, index_source) = self.build_tracks(medium);
+                let mut rems = release_rems.clone();
+                rems.push(Rem::new("DISCNUMBER", position.to_string()));
+                rems.push(Rem::new("TOTALDISCS", media.len().to_string()));
+                rems.push(Rem::new("TOTALTRACKS", tracks.len().to_string()));
+                if_chain! {
+                    if let Some(t) = self.medium_title(medium);
+                    if !t.is_empty();
+                    then {
+                        if self.title_suffix {
+                            title += &format!(": {t}");
+                        }
+                        rems.push(Rem::quoted("DISCSUBTITLE", t));
+                    }
+                }
+                rems.push(Rem::new(INDEX_SOURCE_REM, index_source.as_str()));
+                if let Some(toc) = self.find_toc(medium) {
+                    let disc_ids = DiscIds::new(&toc);
+                    rems.push(Rem::new("DISCID", disc_ids.freedb_string()));
+                    rems.push(Rem::new("MUSICBRAINZ_DISC_ID", disc_ids.musicbrainz));
+                }
+
+                let medium_values = self.medium_values(medium);
+                let image = CueFile {
+                    name: sanitize_file_name(&self.file_template.render(&medium_values)),
+                    file_type: self.file_type.clone(),
+                    leading_indexes: Vec::new(),
+                    tracks,
+                };
+                // generated tracks have no INDEX but the INDEX 01 their file starts at, which cannot be out of order
+                let files = self.split_files(&medium_values, image).expect("generated tracks only have an INDEX 01");
+                let name = sanitize_file_name(&self.output_template.render(&medium_values));
+
+                let cue_sheet = CueSheet {
+                    catalog: self.release.barcode.as_deref().and_then(normalize_barcode),
+                    title: Some(title),
+                    performer: self.album_performer(medium),
+                    rems,
+                    files,
+                    ..Default::default()
+                };
+
+                MediumCueSheet { name, position, cue_sheet }
+            })
+            .collect()
+    }
+
+    /// Splits an image FILE into the files of the layout, naming them after the track file template.
+    fn split_files(&self, medium_values: &[(&'static str, TemplateValue)], image: CueFile) -> Result<Vec<CueFile>, String> {
+        split_image(image, self.layout, |track| {
+            let mut values = medium_values.to_vec();
+            values.push(("track", track.number.into()));
+            values.push(("title", track.title.clone().unwrap_or_default().into()));
+            values.push(("performer", track.performer.clone().unwrap_or_default().into()));
+            sanitize_file_name(&self.track_file_template.render(&values))
+        })
+    }
+
+    /// Splits the single FILE of a rip of the medium at `position` into the files of the layout, if it is not
+    /// [`Layout::Image`]. Unlike generated
+    /// cuesheets, whose tracks only have an INDEX 01, a rip has the INDEX 00 gaps the gap layouts place.
+    pub fn split_rip(&self, rip: &mut CueSheet, position: u32) -> Result<(), String> {
+        // the rip keeps its files, even if there are several
+        if self.layout == Layout::Image {
+            return Ok(());
+        }
+
+        let Some(medium) = self.release.media.iter().flatten().find(|m| m.position == Some(position)) else {
+            return Err(format!("the release has no medium {position}"));
+        };
+        if rip.files.len() != 1 {
+            return Err(format!("only a cuesheet with a single FILE can be split, this one has {}", rip.files.len()));
+        }
+
+        let image = rip.files.remove(0);
+        rip.files = self.split_files(&self.medium_values(medium), image)?;
+        Ok(())
+    }
+
+    fn album_performer(&self, medium: &Media) -> Option<String> {
+        if self.classical {
+            let mut composers = Vec::new();
+            for recording in medium.tracks.iter().flatten().filter_map(|t| t.recording.as_ref()) {
+                for composer in work_artists(recording, COMPOSER_RELATIONS) {
+                    if !composers.contains(&composer) {
+                        composers.push(composer);
+                    }
+                }
+            }
+            if !composers.is_empty() {
+                return Some(composers.join(&self.credit_separator));
+            }
+        }
+
+        self.release.artist_credit.as_deref().map(|a| self.join_artists(a))
+    }
+
+    fn release_rems(&self) -> Vec<Rem> {
+        let mut rems = Vec::new();
+
+        if let (_, Some(title)) = self.album_titles() {
+            rems.push(Rem::quoted("TITLE_LATIN", title));
+        }
+
+        let genres = self.genres.select(self.release);
+        if !genres.is_empty() {
+            rems.push(Rem::new("GENRE", genres.join("; ")));
+        }
+
+        let release_date = self.release.date.as_ref().and_then(format_date);
+        let original_date = self.release.release_group.as_ref().and_then(|g| g.first_release_date.as_ref()).and_then(format_date);
+        let (date, original_date) = match self.date_source {
+            DateSource::Release => (release_date, None),
+            DateSource::ReleaseGroup => (original_date, None),
+            DateSource::Both => (release_date, original_date),
+        };
+        if let Some(date) = date {
+            rems.push(Rem::new("DATE", date));
+        }
+        if let Some(original_date) = original_date {
+            rems.push(Rem::new("ORIGINALDATE", original_date));
+        }
+        if let Some(country) = &self.release.country {
+            rems.push(Rem::new("RELEASECOUNTRY", country));
+        }
+        if let Some(status) = self.release.status.as_ref().and_then(release_status_name) {
+            rems.push(Rem::new("RELEASESTATUS", status));
+        }
+        if self.is_compilation() {
+            rems.push(Rem::new("COMPILATION", "1"));
+        }
+
+        // a release lists the same label once per catalog number and vice versa
+        let mut labels = Vec::new();
+        let mut catalog_numbers = Vec::new();
+        for l in self.release.label_info.iter().flatten() {
+            if let Some(li) = &l.label {
+                if !li.name.is_empty() && !labels.contains(&li.name.as_str()) {
+                    labels.push(li.name.as_str());
+                }
+            }
+            if let Some(catalog_number) = &l.catalog_number {
+                if is_catalog_number(catalog_number) && !catalog_numbers.contains(&catalog_number.as_str()) {
+                    catalog_numbers.push(catalog_number.as_str());
+                }
+            }
+        }
+        if !labels.is_empty() {
+            rems.push(Rem::quoted("LABEL", labels.join("; ")));
+        }
+        if !catalog_numbers.is_empty() {
+            rems.push(Rem::quoted("CATALOGNUMBER", catalog_numbers.join("; ")));
+        }
+        if let Some(comment) = &self.comment {
+            rems.push(Rem::quoted("COMMENT", comment));
+        }
+
+        if let Some(barcode) = &self.release.barcode {
+            if !barcode.is_empty() && normalize_barcode(barcode).is_none() {
+                rems.push(Rem::new("BARCODE", barcode));
+            }
+        }
+
+        if let Some(artists) = &self.release.artist_credit {
+            rems.push(Rem::quoted("ALBUMARTISTSORT", self.artist_name.join_sort(artists, self.artist_aliases)));
+        }
+
+        // besides MUSICBRAINZ_ALBUM_ID, the IDs are named like the tags Picard writes
+        rems.push(Rem::new("MUSICBRAINZ_ALBUM_ID", &self.release.id));
+        if let Some(artists) = &self.release.artist_credit {
+            rems.push(Rem::new("MUSICBRAINZ_ALBUMARTISTID", join_artist_ids(artists)));
+        }
+        if let Some(release_group) = &self.release.release_group {
+            rems.push(Rem::new("MUSICBRAINZ_RELEASEGROUPID", &release_group.id));
+        }
+        rems
+    }
+
+    /// The given TOC or the TOC of the first disc ID attached to the medium, if it has as many tracks as the medium.
+    fn find_toc(&self, medium: &Media) -> Option<Toc> {
+        let track_count = medium.tracks.as_deref().unwrap_or_default().len();
+        let mut tocs = self.toc.iter().cloned().chain(medium.discs.iter().flatten().map(Toc::from_disc));
+        tocs.find(|t| t.offsets.len() == track_count)
+    }
+
+    /// Returns the INDEX 01 position of every track of the medium, preferring the given TOC or the TOC of an
+    /// attached disc ID over the track lengths. Without a TOC, the positions stop at the track after the first track
+    /// without a length.
+    pub fn track_starts(&self, medium: &Media) -> (Vec<CueTime>, IndexSource) {
+        if let Some(toc) = self.find_toc(medium) {
+            return (toc.track_starts(), IndexSource::Toc);
+        }
+
+        let mut starts = Vec::new();
+        let mut track_start = 0;
+        for track in medium.tracks.iter().flatten() {
+            starts.push(CueTime::from_milliseconds(track_start));
+            match track.length {
+                Some(length) => track_start += length,
+                None => break,
+            }
+        }
+        (starts, IndexSource::TrackLengths)
+    }
+
+    fn build_tracks(&self, medium: &Media) -> (Vec<CueTrack>, IndexSource) {
+        let (track_starts, index_source) = self.track_starts(medium);
+        let pseudo_tracks = self.pseudo_medium(medium).and_then(|m| m.tracks.as_deref()).unwrap_or_default();
+        // the release artist, if tracks credited to it get no PERFORMER
+        let album_artists = if self.omit_album_performer && !self.is_compilation() {
+            self.release.artist_credit.as_deref().map(|a| self.join_artists(a))
+        } else {
+            None
+        };
+
+        let tracks = medium
+            .tracks
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, track)| {
+                let mut cue_track = CueTrack::audio(track.position);
+                let latin = pseudo_tracks.iter().find(|t| t.position == track.position).map(|t| t.title.as_str());
+                let (title, title_latin) = self.titles(&track.title, latin);
+                cue_track.title = Some(title);
+                if let Some(title) = title_latin {
+                    cue_track.rems.push(Rem::quoted("TITLE_LATIN", title));
+                }
+                let artists = self.track_artists(track);
+                cue_track.performer = artists.map(|a| self.join_artists(a)).filter(|p| album_artists.as_ref() != Some(p));
+                if let Some(artists) = artists {
+                    cue_track.rems.push(Rem::quoted("ARTISTSORT", self.artist_name.join_sort(artists, self.artist_aliases)));
+                }
+                if let Some(recording) = &track.recording {
+                    cue_track.rems.push(Rem::new("MUSICBRAINZ_TRACKID", &recording.id));
+                }
+                cue_track.rems.push(Rem::new("MUSICBRAINZ_RELEASETRACKID", &track.id));
+                if let Some(artists) = artists {
+                    cue_track.rems.push(Rem::new("MUSICBRAINZ_ARTISTID", join_artist_ids(artists)));
+                }
+
+                // a recording can have several ISRCs, e.g. after a remaster; pick the lowest one so that repeated runs
+                // agree, and list all of them
+                let mut isrcs = track
+                    .recording
+                    .iter()
+                    .flat_map(|r| r.isrcs.iter().flatten())
+                    .filter_map(|i| normalize_isrc(i))
+                    .collect::<Vec<_>>();
+                isrcs.sort();
+                isrcs.dedup();
+                if isrcs.len() > 1 {
+                    cue_track.rems.push(Rem::new("ISRCS", isrcs.join(" ")));
+                }
+                cue_track.isrc = isrcs.into_iter().next();
+
+                if let Some(recording) = &track.recording {
+                    let composers = work_artists(recording, COMPOSER_RELATIONS);
+                    let lyricists = work_artists(recording, LYRICIST_RELATIONS);
+                    let mut songwriters = composers.clone();
+                    songwriters.extend(lyricists.iter().filter(|l| !composers.contains(l)).cloned());
+
+                    if !songwriters.is_empty() {
+                        cue_track.songwriter = Some(songwriters.join(&self.credit_separator));
+                    }
+                    if !composers.is_empty() {
+                        cue_track.rems.push(Rem::quoted("COMPOSER", composers.join(&self.credit_separator)));
+                    }
+                    if !lyricists.is_empty() {
+                        cue_track.rems.push(Rem::quoted("LYRICIST", lyricists.join(&self.credit_separator)));
+                    }
+                    if self.classical {
+                        cue_track.rems.extend(self.classical_rems(recording));
+                    }
+                }
+                if let Some(&time) = track_starts.get(i) {
+                    cue_track.indexes.push(CueIndex { number: 1, time });
+                }
+                cue_track
+            })
+            .collect();
+        (tracks, index_source)
+    }
+
+    /// REM entries naming the work and movement of the recording and who performed it.
+    fn classical_rems(&self, recording: &Recording) -> Vec<Rem> {
+        let mut rems = Vec::new();
+
+        if let Some(work) = recording_works(recording).next() {
+            match parent_work(work) {
+                Some((parent, position)) => {
+                    // movements are usually titled "Symphony No. 5: I. Allegro con brio"
+                    let name = work.title.strip_prefix(parent.title.as_str()).and_then(|t| t.strip_prefix(": ")).unwrap_or(&work.title);
+                    let numbered = split_movement_number(name);
+
+                    rems.push(Rem::quoted("WORK", &parent.title));
+                    rems.push(Rem::quoted("MOVEMENTNAME", numbered.map_or(name, |(_, n)| n)));
+                    if let Some(number) = position.map(|p| p as u32).or(numbered.map(|(n, _)| n)) {
+                        rems.push(Rem::new("MOVEMENT", number.to_string()));
+                    }
+                }
+                None => rems.push(Rem::quoted("WORK", &work.title)),
+            }
+        }
+
+        let performers = [
+            ("CONDUCTOR", related_artists(recording.relations.iter().flatten(), &["conductor"])),
+            ("ORCHESTRA", related_artists(recording.relations.iter().flatten(), &["performing orchestra"])),
+            ("SOLOISTS", soloists(recording)),
+        ];
+        for (name, artists) in performers {
+            if !artists.is_empty() {
+                rems.push(Rem::quoted(name, artists.join(&self.credit_separator)));
+            }
+        }
+
+        rems
+    }
+}
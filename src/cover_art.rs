@@ -0,0 +1,42 @@
+use std::path::Path;
+use std::time::Duration;
+
+use musicbrainz_rs::entity::release::Release;
+use musicbrainz_rs::entity::CoverartResponse;
+use musicbrainz_rs::{FetchCoverart, MusicBrainzClient};
+
+const COVER_ART_PATH_COMPONENT: &str = "Cover";
+
+fn download_image(url: &str, output_path_prefix: &Path) {
+    let resp = reqwest::blocking::get(url).unwrap();
+    if resp.status().is_success() {
+        let file_extension = Path::new(resp.url().path()).extension().unwrap().to_string_lossy();
+        let output_path = output_path_prefix.with_extension(&*file_extension);
+        std::fs::write(output_path, resp.bytes().unwrap()).unwrap();
+    } else {
+        eprintln!("HTTP error code {}", resp.status());
+    }
+}
+
+/// Downloads all cover art images of a release into `out_dir`.
+pub fn download_cover_art(client: &MusicBrainzClient, release_id: &str, out_dir: &Path) {
+    let cover_art_path = out_dir.join(COVER_ART_PATH_COMPONENT);
+    if let Ok(resp) = Release::fetch_coverart().id(release_id).execute_with_client(client) {
+        match resp {
+            CoverartResponse::Url(cover_art_url) => {
+                download_image(&cover_art_url, &cover_art_path);
+            }
+            CoverartResponse::Json(cover_art) => {
+                std::fs::create_dir_all(&cover_art_path).unwrap();
+
+                for img in cover_art.images {
+                    let img_filename_stem = img.types.iter().map(|t| format!("{t:#?}")).collect::<Vec<_>>().join("_");
+                    download_image(&img.image, &cover_art_path.join(img_filename_stem));
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        }
+    } else {
+        eprintln!("Failed to download cover art")
+    }
+}
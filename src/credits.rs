@@ -0,0 +1,108 @@
+use musicbrainz_rs::entity::recording::Recording;
+use musicbrainz_rs::entity::relations::{Relation, RelationContent};
+use musicbrainz_rs::entity::work::Work;
+
+/// Work relationship types crediting the music.
+pub const COMPOSER_RELATIONS: &[&str] = &["composer", "writer"];
+
+/// Work relationship types crediting the words.
+pub const LYRICIST_RELATIONS: &[&str] = &["lyricist", "librettist", "writer"];
+
+/// Recording relationship types crediting soloists, whose instrument or voice is named by the attributes.
+pub const SOLOIST_RELATIONS: &[&str] = &["instrument", "vocal"];
+
+/// The works performed in the recording, as linked by recording-level work relationships.
+pub fn recording_works(recording: &Recording) -> impl Iterator<Item = &Work> {
+    recording.relations.iter().flatten().filter_map(|r| match &r.content {
+        RelationContent::Work(work) => Some(work.as_ref()),
+        _ => None,
+    })
+}
+
+/// Name of the artist a relationship points to, as credited on the relationship if it was credited differently.
+pub fn relation_artist_name(relation: &Relation) -> Option<String> {
+    match &relation.content {
+        RelationContent::Artist(artist) => match &relation.target_credit {
+            Some(credit) if !credit.is_empty() => Some(credit.clone()),
+            _ => Some(artist.name.clone()),
+        },
+        _ => None,
+    }
+}
+
+/// Names of the artists related to the given relations by one of `relation_types`, without duplicates and in the
+/// order MusicBrainz lists them.
+pub fn related_artists<'a>(relations: impl IntoIterator<Item = &'a Relation>, relation_types: &[&str]) -> Vec<String> {
+    let mut artists = Vec::new();
+    for relation in relations {
+        if !relation_types.contains(&relation.relation_type.as_str()) {
+            continue;
+        }
+        if let Some(name) = relation_artist_name(relation) {
+            if !artists.contains(&name) {
+                artists.push(name);
+            }
+        }
+    }
+    artists
+}
+
+/// Names of the artists credited with one of `relation_types` on any work performed in the recording.
+pub fn work_artists(recording: &Recording, relation_types: &[&str]) -> Vec<String> {
+    related_artists(recording_works(recording).flat_map(|w| w.relations.iter().flatten()), relation_types)
+}
+
+/// The work `work` is a part of, such as the symphony of a movement, along with the position of `work` in it.
+pub fn parent_work(work: &Work) -> Option<(&Work, Option<u64>)> {
+    work.relations.iter().flatten().find_map(|r| match &r.content {
+        RelationContent::Work(parent) if r.relation_type == "parts" && r.direction == "backward" => {
+            Some((parent.as_ref(), r.ordering_key))
+        }
+        _ => None,
+    })
+}
+
+/// Names of the soloists of the recording followed by their instruments, e.g. "Martha Argerich (piano)".
+pub fn soloists(recording: &Recording) -> Vec<String> {
+    let mut soloists = Vec::new();
+    for relation in recording.relations.iter().flatten() {
+        if !SOLOIST_RELATIONS.contains(&relation.relation_type.as_str()) {
+            continue;
+        }
+        if let Some(name) = relation_artist_name(relation) {
+            let soloist = match relation.attributes.as_deref() {
+                Some(attributes) if !attributes.is_empty() => format!("{name} ({})", attributes.join(", ")),
+                _ => name,
+            };
+            if !soloists.contains(&soloist) {
+                soloists.push(soloist);
+            }
+        }
+    }
+    soloists
+}
+
+/// Parses the Roman numeral a movement title starts with, as in "IV. Allegro", returning its value and the rest of
+/// the title.
+pub fn split_movement_number(title: &str) -> Option<(u32, &str)> {
+    let (numeral, rest) = title.split_once(". ")?;
+    let mut value = 0;
+    let mut previous = 0;
+    for c in numeral.chars().rev() {
+        let digit = match c {
+            'I' => 1,
+            'V' => 5,
+            'X' => 10,
+            'L' => 50,
+            'C' => 100,
+            _ => return None,
+        };
+        if digit < previous {
+            value -= digit;
+        } else {
+            value += digit;
+            previous = digit;
+        }
+    }
+    (value > 0).then_some((value, rest))
+}
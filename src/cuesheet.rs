@@ -0,0 +1,196 @@
+use std::fmt;
+use std::str::FromStr;
+
+// From https://wiki.hydrogenaud.io/index.php?title=Cue_sheet:
+// FF the number of frames (there are seventy five frames to one second)
+pub const FRAMES_PER_SECOND: u32 = 75;
+
+// Disc TOC offsets include the 2 seconds lead-in before the first track
+pub const LEAD_IN_FRAMES: u32 = 150;
+
+/// A position or duration in a cuesheet, stored as CD frames.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CueTime {
+    pub frames: u32,
+}
+
+impl CueTime {
+    pub fn from_frames(frames: u32) -> Self {
+        Self { frames }
+    }
+
+    pub fn from_msf(minutes: u32, seconds: u32, frames: u32) -> Self {
+        Self::from_frames((minutes * 60 + seconds) * FRAMES_PER_SECOND + frames)
+    }
+
+    pub fn from_milliseconds(ms: u32) -> Self {
+        const MILLISECONDS_PER_FRAME: f64 = 1000.0 / FRAMES_PER_SECOND as f64;
+
+        let ms_part = (ms % 1000) as f64;
+        Self::from_frames(ms / 1000 * FRAMES_PER_SECOND + (ms_part / MILLISECONDS_PER_FRAME).round() as u32)
+    }
+}
+
+impl fmt::Display for CueTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frames = self.frames % FRAMES_PER_SECOND;
+        let seconds = self.frames / FRAMES_PER_SECOND % 60;
+        let minutes = self.frames / FRAMES_PER_SECOND / 60;
+
+        write!(f, "{minutes:02}:{seconds:02}:{frames:02}")
+    }
+}
+
+impl FromStr for CueTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':').map(|p| p.parse::<u32>().ok());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(minutes)), Some(Some(seconds)), Some(Some(frames)), None) if seconds < 60 && frames < FRAMES_PER_SECOND => {
+                // the minutes are not bounded, so the frame count can overflow
+                minutes
+                    .checked_mul(60 * FRAMES_PER_SECOND)
+                    .and_then(|f| f.checked_add(seconds * FRAMES_PER_SECOND + frames))
+                    .map(Self::from_frames)
+                    .ok_or_else(|| format!("time \"{s}\" is out of range"))
+            }
+            _ => Err(format!("invalid time \"{s}\", expected MM:SS:FF")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rem {
+    pub name: String,
+    pub value: String,
+    pub quoted: bool,
+}
+
+impl Rem {
+    pub fn new(name: &str, value: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.into(),
+            quoted: false,
+        }
+    }
+
+    pub fn quoted(name: &str, value: impl Into<String>) -> Self {
+        Self {
+            quoted: true,
+            ..Self::new(name, value)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueIndex {
+    pub number: u32,
+    pub time: CueTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueTrack {
+    pub number: u32,
+    pub data_type: String,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub songwriter: Option<String>,
+    pub isrc: Option<String>,
+    pub flags: Vec<String>,
+    pub rems: Vec<Rem>,
+    pub pregap: Option<CueTime>,
+    pub indexes: Vec<CueIndex>,
+    pub postgap: Option<CueTime>,
+}
+
+impl CueTrack {
+    pub fn audio(number: u32) -> Self {
+        Self {
+            number,
+            data_type: "AUDIO".to_string(),
+            title: None,
+            performer: None,
+            songwriter: None,
+            isrc: None,
+            flags: Vec::new(),
+            rems: Vec::new(),
+            pregap: None,
+            indexes: Vec::new(),
+            postgap: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileType {
+    Wave,
+    Mp3,
+    Aiff,
+    Binary,
+    Motorola,
+    /// A type outside of the cue specification that some rippers write, e.g. "FLAC".
+    Other(String),
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Wave => "WAVE",
+            Self::Mp3 => "MP3",
+            Self::Aiff => "AIFF",
+            Self::Binary => "BINARY",
+            Self::Motorola => "MOTOROLA",
+            Self::Other(s) => s,
+        })
+    }
+}
+
+impl FromStr for FileType {
+    type Err = String;
+
+    /// Parses one of the file types of the cue specification.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "WAVE" => Ok(Self::Wave),
+            "MP3" => Ok(Self::Mp3),
+            "AIFF" => Ok(Self::Aiff),
+            "BINARY" => Ok(Self::Binary),
+            "MOTOROLA" => Ok(Self::Motorola),
+            _ => Err(format!("invalid file type \"{s}\", expected one of WAVE, MP3, AIFF, BINARY, MOTOROLA")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueFile {
+    pub name: String,
+    pub file_type: FileType,
+    /// INDEX entries of the previous file's last track that lie in this file, as written by rippers for
+    /// gap-appended layouts (e.g. INDEX 01 following an INDEX 00 in the previous file).
+    pub leading_indexes: Vec<CueIndex>,
+    pub tracks: Vec<CueTrack>,
+}
+
+/// A single cuesheet, which describes one medium of a release.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CueSheet {
+    pub catalog: Option<String>,
+    pub cdtextfile: Option<String>,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub songwriter: Option<String>,
+    pub rems: Vec<Rem>,
+    pub files: Vec<CueFile>,
+}
+
+impl CueSheet {
+    pub fn tracks(&self) -> impl Iterator<Item = &CueTrack> {
+        self.files.iter().flat_map(|f| &f.tracks)
+    }
+
+    pub fn tracks_mut(&mut self) -> impl Iterator<Item = &mut CueTrack> {
+        self.files.iter_mut().flat_map(|f| &mut f.tracks)
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+/// A MusicBrainz date, which can be as precise as a day or only name the year or month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialDate {
+    Year(i32),
+    YearMonth(i32, u32),
+    Date(NaiveDate),
+}
+
+impl FromStr for PartialDate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split('-').collect::<Vec<_>>();
+        let number = |part: &str, digits: usize| {
+            if part.len() == digits && part.bytes().all(|b| b.is_ascii_digit()) {
+                part.parse::<u32>().map_err(|e| e.to_string())
+            } else {
+                Err(format!("invalid date \"{s}\""))
+            }
+        };
+
+        match parts[..] {
+            [year] => Ok(Self::Year(number(year, 4)? as i32)),
+            [year, month] => {
+                let (year, month) = (number(year, 4)? as i32, number(month, 2)?);
+                if (1..=12).contains(&month) {
+                    Ok(Self::YearMonth(year, month))
+                } else {
+                    Err(format!("invalid month in date \"{s}\""))
+                }
+            }
+            [year, month, day] => NaiveDate::from_ymd_opt(number(year, 4)? as i32, number(month, 2)?, number(day, 2)?)
+                .map(Self::Date)
+                .ok_or_else(|| format!("invalid day in date \"{s}\"")),
+            _ => Err(format!("invalid date \"{s}\"")),
+        }
+    }
+}
+
+impl fmt::Display for PartialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Year(year) => write!(f, "{year:04}"),
+            Self::YearMonth(year, month) => write!(f, "{year:04}-{month:02}"),
+            Self::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+        }
+    }
+}
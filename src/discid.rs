@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use sha1::{Digest, Sha1};
+
+use crate::cuesheet::{FRAMES_PER_SECOND, LEAD_IN_FRAMES};
+use crate::toc::Toc;
+
+/// The identifiers a CD is known by in the MusicBrainz, FreeDB and AccurateRip databases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscIds {
+    pub musicbrainz: String,
+    pub freedb: u32,
+    pub accuraterip: (u32, u32),
+    pub track_count: u32,
+}
+
+impl DiscIds {
+    pub fn new(toc: &Toc) -> Self {
+        Self {
+            musicbrainz: musicbrainz_disc_id(toc),
+            freedb: freedb_id(toc),
+            accuraterip: accuraterip_ids(toc),
+            track_count: toc.offsets.len() as u32,
+        }
+    }
+
+    pub fn freedb_string(&self) -> String {
+        format!("{:08X}", self.freedb)
+    }
+
+    /// The name of the AccurateRip database file for the disc, e.g. `dBAR-012-001a2b3c-00c4d5e6-9a0b5e0c.bin`.
+    pub fn accuraterip_string(&self) -> String {
+        format!(
+            "dBAR-{:03}-{:08x}-{:08x}-{:08x}.bin",
+            self.track_count, self.accuraterip.0, self.accuraterip.1, self.freedb
+        )
+    }
+}
+
+// From https://musicbrainz.org/doc/Disc_ID_Calculation:
+// SHA-1 of the hex TOC with 99 track offsets, encoded in base64 with "+/=" replaced by "._-"
+pub fn musicbrainz_disc_id(toc: &Toc) -> String {
+    let mut toc_hex = format!("{:02X}{:02X}{:08X}", toc.first_track, toc.last_track, toc.leadout);
+    for track in 1..100u32 {
+        let offset = track.checked_sub(toc.first_track).and_then(|i| toc.offsets.get(i as usize));
+        write!(toc_hex, "{:08X}", offset.copied().unwrap_or_default()).unwrap();
+    }
+
+    STANDARD
+        .encode(Sha1::digest(toc_hex.as_bytes()))
+        .replace('+', ".")
+        .replace('/', "_")
+        .replace('=', "-")
+}
+
+pub fn freedb_id(toc: &Toc) -> u32 {
+    fn digit_sum(mut n: u32) -> u32 {
+        let mut sum = 0;
+        while n > 0 {
+            sum += n % 10;
+            n /= 10;
+        }
+        sum
+    }
+
+    let checksum = toc.offsets.iter().map(|o| digit_sum(o / FRAMES_PER_SECOND)).sum::<u32>();
+    let length = toc.leadout / FRAMES_PER_SECOND - toc.offsets.first().copied().unwrap_or_default() / FRAMES_PER_SECOND;
+    (checksum % 0xff) << 24 | length << 8 | toc.offsets.len() as u32
+}
+
+/// The two AccurateRip disc IDs; the third one AccurateRip uses is the FreeDB ID.
+pub fn accuraterip_ids(toc: &Toc) -> (u32, u32) {
+    let mut id1 = 0u32;
+    let mut id2 = 0u32;
+    for (i, offset) in toc.offsets.iter().enumerate() {
+        let lba = offset.saturating_sub(LEAD_IN_FRAMES);
+        id1 = id1.wrapping_add(lba);
+        id2 = id2.wrapping_add(lba.max(1).wrapping_mul(i as u32 + 1));
+    }
+
+    let leadout_lba = toc.leadout.saturating_sub(LEAD_IN_FRAMES);
+    id1 = id1.wrapping_add(leadout_lba);
+    id2 = id2.wrapping_add(leadout_lba.wrapping_mul(toc.offsets.len() as u32 + 1));
+    (id1, id2)
+}
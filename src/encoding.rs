@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use encoding_rs::{Encoding, SHIFT_JIS, WINDOWS_1252};
+
+use crate::cuesheet::CueSheet;
+use crate::escape::{rewrite_cue_sheet, RewrittenField};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Text encoding of a written cuesheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// UTF-8 with a byte order mark, which makes foobar2000 and Windows players detect UTF-8.
+    Utf8Bom,
+    Windows1252,
+    ShiftJis,
+    /// ISO-8859-1, i.e. the first 256 code points of Unicode.
+    Latin1,
+}
+
+impl FromStr for TextEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "utf8" => Ok(Self::Utf8),
+            "utf8-bom" => Ok(Self::Utf8Bom),
+            "windows-1252" => Ok(Self::Windows1252),
+            "shift-jis" => Ok(Self::ShiftJis),
+            "latin1" => Ok(Self::Latin1),
+            _ => Err(format!("invalid encoding \"{s}\", expected one of utf8, utf8-bom, windows-1252, shift-jis, latin1")),
+        }
+    }
+}
+
+impl TextEncoding {
+    fn legacy_encoding(&self) -> Option<&'static Encoding> {
+        match self {
+            Self::Windows1252 => Some(WINDOWS_1252),
+            Self::ShiftJis => Some(SHIFT_JIS),
+            _ => None,
+        }
+    }
+
+    pub fn can_encode(&self, c: char) -> bool {
+        match self {
+            Self::Utf8 | Self::Utf8Bom => true,
+            Self::Latin1 => (c as u32) <= 0xFF,
+            Self::Windows1252 | Self::ShiftJis => {
+                let (_, _, unmappable) = self.legacy_encoding().unwrap().encode(c.encode_utf8(&mut [0; 4]));
+                !unmappable
+            }
+        }
+    }
+
+    /// Encodes `text`, transliterating the characters the encoding cannot represent.
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let text = self.transliterate(text);
+        match self {
+            Self::Utf8 => text.into_bytes(),
+            Self::Utf8Bom => [UTF8_BOM, text.as_bytes()].concat(),
+            Self::Latin1 => text.chars().map(|c| c as u8).collect(),
+            Self::Windows1252 | Self::ShiftJis => self.legacy_encoding().unwrap().encode(&text).0.into_owned(),
+        }
+    }
+
+    /// Replaces every character the encoding cannot represent with its ASCII transliteration, e.g. "ő" with "o" or
+    /// "東" with "Dong", or with "?" if there is none. Quotation marks such as "”" become "'" rather than a double quote,
+    /// which would end the quoted field it is written in.
+    pub fn transliterate(&self, text: &str) -> String {
+        let mut transliterated = String::with_capacity(text.len());
+        for c in text.chars() {
+            if self.can_encode(c) {
+                transliterated.push(c);
+            } else {
+                match deunicode::deunicode_char(c) {
+                    Some(ascii) if !ascii.is_empty() => transliterated.push_str(&ascii.replace('"', "'")),
+                    _ => transliterated.push('?'),
+                }
+            }
+        }
+        transliterated
+    }
+}
+
+/// Transliterates every field of the cuesheet that `encoding` cannot represent, returning the fields that lost
+/// information. FILE names cannot be transliterated, since the cuesheet would then point to a file that does not exist,
+/// so a FILE name the encoding cannot represent is an error.
+pub fn transliterate_cue_sheet(cue_sheet: &mut CueSheet, encoding: TextEncoding) -> Result<Vec<RewrittenField>, String> {
+    if let Some(file) = cue_sheet.files.iter().find(|file| !file.name.chars().all(|c| encoding.can_encode(c))) {
+        return Err(format!("the file name \"{}\" cannot be written in this encoding", file.name));
+    }
+    Ok(rewrite_cue_sheet(cue_sheet, |value| encoding.transliterate(value)))
+}
+
+/// Decodes a cuesheet read from disk. Byte order marks select UTF-8 or UTF-16; without one, text that is not valid
+/// UTF-8 is taken as Windows-1252, which EAC writes unless its UTF-8 output is enabled.
+pub fn decode(bytes: &[u8]) -> String {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+        return encoding.decode_without_bom_handling(&bytes[bom_length..]).0.into_owned();
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => WINDOWS_1252.decode_without_bom_handling(bytes).0.into_owned(),
+    }
+}
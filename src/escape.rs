@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use crate::cuesheet::{CueSheet, Rem};
+
+/// How double quotes inside a quoted field are rewritten, since the cuesheet format has no way to escape them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotePolicy {
+    /// Replace with “ and ”, depending on whether the quote opens or closes.
+    Typographic,
+    /// Replace with '.
+    Single,
+    /// Remove the quotes.
+    Strip,
+}
+
+impl FromStr for QuotePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "typographic" => Ok(Self::Typographic),
+            "single" => Ok(Self::Single),
+            "strip" => Ok(Self::Strip),
+            _ => Err(format!("invalid quote policy \"{s}\", expected one of typographic, single, strip")),
+        }
+    }
+}
+
+/// A field whose value had to be changed to be written to a cuesheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewrittenField {
+    /// The command of the field, prefixed by its track for track-level fields, e.g. "TRACK 03 TITLE".
+    pub field: String,
+    pub original: String,
+    pub rewritten: String,
+}
+
+/// Rewrites `value` so that it can be put between double quotes: line breaks and tabs become a single space, other
+/// control characters are removed and double quotes are replaced according to `policy`.
+pub fn escape_field(value: &str, policy: QuotePolicy) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut previous = None;
+
+    for c in value.chars() {
+        match c {
+            '"' => match policy {
+                QuotePolicy::Typographic => {
+                    let opening = previous.is_none_or(|p: char| p.is_whitespace() || "([{".contains(p));
+                    escaped.push(if opening { '“' } else { '”' });
+                }
+                QuotePolicy::Single => escaped.push('\''),
+                QuotePolicy::Strip => {}
+            },
+            '\r' | '\n' | '\t' => {
+                if !matches!(previous, Some('\r' | '\n' | '\t')) {
+                    escaped.push(' ');
+                }
+            }
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+        previous = Some(c);
+    }
+
+    escaped
+}
+
+struct Rewriter<F> {
+    rewrite: F,
+    rewritten_fields: Vec<RewrittenField>,
+}
+
+impl<F: FnMut(&str) -> String> Rewriter<F> {
+    fn rewrite(&mut self, field: String, value: &mut String) {
+        let rewritten = (self.rewrite)(value);
+        if rewritten != *value {
+            self.rewritten_fields.push(RewrittenField {
+                field,
+                original: std::mem::replace(value, rewritten.clone()),
+                rewritten,
+            });
+        }
+    }
+
+    fn rewrite_option(&mut self, field: String, value: &mut Option<String>) {
+        if let Some(value) = value {
+            self.rewrite(field, value);
+        }
+    }
+
+    fn rewrite_rems(&mut self, prefix: &str, rems: &mut [Rem]) {
+        for rem in rems {
+            self.rewrite(format!("{prefix}REM {}", rem.name), &mut rem.value);
+        }
+    }
+}
+
+/// Applies `rewrite` to every quoted field, FILE name and REM value of the cuesheet, returning the fields that changed.
+pub fn rewrite_cue_sheet(cue_sheet: &mut CueSheet, rewrite: impl FnMut(&str) -> String) -> Vec<RewrittenField> {
+    let mut rewriter = Rewriter {
+        rewrite,
+        rewritten_fields: Vec::new(),
+    };
+
+    rewriter.rewrite_option("CDTEXTFILE".to_string(), &mut cue_sheet.cdtextfile);
+    rewriter.rewrite_option("TITLE".to_string(), &mut cue_sheet.title);
+    rewriter.rewrite_option("PERFORMER".to_string(), &mut cue_sheet.performer);
+    rewriter.rewrite_option("SONGWRITER".to_string(), &mut cue_sheet.songwriter);
+    rewriter.rewrite_rems("", &mut cue_sheet.rems);
+
+    for file in &mut cue_sheet.files {
+        rewriter.rewrite("FILE".to_string(), &mut file.name);
+
+        for track in &mut file.tracks {
+            let prefix = format!("TRACK {:02} ", track.number);
+            rewriter.rewrite_option(format!("{prefix}TITLE"), &mut track.title);
+            rewriter.rewrite_option(format!("{prefix}PERFORMER"), &mut track.performer);
+            rewriter.rewrite_option(format!("{prefix}SONGWRITER"), &mut track.songwriter);
+            rewriter.rewrite_rems(&prefix, &mut track.rems);
+        }
+    }
+
+    rewriter.rewritten_fields
+}
+
+/// Escapes every quoted field and REM value of the cuesheet with [`escape_field`], returning the fields that changed.
+pub fn escape_cue_sheet(cue_sheet: &mut CueSheet, policy: QuotePolicy) -> Vec<RewrittenField> {
+    rewrite_cue_sheet(cue_sheet, |value| escape_field(value, policy))
+}
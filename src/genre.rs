@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use musicbrainz_rs::entity::genre::Genre;
+use musicbrainz_rs::entity::release::Release;
+use musicbrainz_rs::entity::tag::Tag;
+
+/// Where the genres written as REM GENRE come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenreSource {
+    ReleaseGroup,
+    Release,
+    /// The genres of the artists credited on the release.
+    Artist,
+    /// The folksonomy tags of the release group, which are not restricted to genres.
+    Tags,
+}
+
+impl FromStr for GenreSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "release-group" => Ok(Self::ReleaseGroup),
+            "release" => Ok(Self::Release),
+            "artist" => Ok(Self::Artist),
+            "tags" => Ok(Self::Tags),
+            _ => Err(format!("invalid genre source \"{s}\", expected one of release-group, release, artist, tags")),
+        }
+    }
+}
+
+/// Maps genre names to the canonical names of a library, dropping every genre it does not list.
+///
+/// Each line of the map is either a canonical name, allowing that genre, or `name = canonical name`. Names are
+/// matched case-insensitively; empty lines and lines starting with "#" are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenreMap {
+    entries: Vec<(String, String)>,
+}
+
+impl FromStr for GenreMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, canonical) = line.split_once('=').unwrap_or((line, line));
+            let (name, canonical) = (name.trim(), canonical.trim());
+            if name.is_empty() || canonical.is_empty() {
+                return Err(format!("line {}: empty genre name", i + 1));
+            }
+            entries.push((name.to_lowercase(), canonical.to_string()));
+        }
+        Ok(Self { entries })
+    }
+}
+
+impl GenreMap {
+    pub fn map(&self, genre: &str) -> Option<&str> {
+        let genre = genre.to_lowercase();
+        self.entries.iter().find(|(name, _)| *name == genre).map(|(_, canonical)| canonical.as_str())
+    }
+}
+
+/// How the genres of a release are picked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenreOptions {
+    pub source: GenreSource,
+    /// Genres with fewer votes are dropped.
+    pub min_votes: u32,
+    /// Keep at most this many genres, the ones with the most votes.
+    pub limit: Option<usize>,
+    pub map: Option<GenreMap>,
+}
+
+impl Default for GenreOptions {
+    fn default() -> Self {
+        Self {
+            source: GenreSource::ReleaseGroup,
+            min_votes: 0,
+            limit: None,
+            map: None,
+        }
+    }
+}
+
+fn genre_votes(genres: &[Genre]) -> Vec<(String, u32)> {
+    genres.iter().map(|g| (g.name.clone(), g.count.unwrap_or_default())).collect()
+}
+
+fn tag_votes(tags: &[Tag]) -> Vec<(String, u32)> {
+    tags.iter().map(|t| (t.name.clone(), t.count.unwrap_or_default().max(0) as u32)).collect()
+}
+
+impl GenreOptions {
+    /// The genre names of the release from the selected source, most voted first.
+    pub fn select(&self, release: &Release) -> Vec<String> {
+        let candidates = match self.source {
+            GenreSource::ReleaseGroup => {
+                release.release_group.iter().flat_map(|g| g.genres.as_deref().map(genre_votes)).flatten().collect()
+            }
+            GenreSource::Release => release.genres.as_deref().map(genre_votes).unwrap_or_default(),
+            GenreSource::Artist => {
+                release.artist_credit.iter().flatten().flat_map(|a| a.artist.genres.as_deref().map(genre_votes)).flatten().collect()
+            }
+            GenreSource::Tags => release.release_group.iter().flat_map(|g| g.tags.as_deref().map(tag_votes)).flatten().collect(),
+        };
+        self.select_from(candidates)
+    }
+
+    /// Filters, maps and sorts genre names given with their vote counts.
+    pub fn select_from(&self, candidates: Vec<(String, u32)>) -> Vec<String> {
+        let mut selected: Vec<(String, u32)> = Vec::new();
+        for (name, votes) in candidates {
+            if votes < self.min_votes {
+                continue;
+            }
+            let name = match &self.map {
+                Some(map) => match map.map(&name) {
+                    Some(canonical) => canonical.to_string(),
+                    None => continue,
+                },
+                None => name,
+            };
+
+            // several genres can map to the same canonical genre, which then gets the most votes among them
+            match selected.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, v)) => *v = (*v).max(votes),
+                None => selected.push((name, votes)),
+            }
+        }
+
+        selected.sort_by_key(|(_, votes)| std::cmp::Reverse(*votes));
+        if let Some(limit) = self.limit {
+            selected.truncate(limit);
+        }
+        selected.into_iter().map(|(name, _)| name).collect()
+    }
+}
@@ -0,0 +1,12 @@
+/// Returns the ISRC in its 12-character form without hyphens if it is valid: a two-letter country code, a
+/// three-character registrant code, a two-digit year and a five-digit designation code.
+pub fn normalize_isrc(isrc: &str) -> Option<String> {
+    let isrc = isrc.replace('-', "").to_ascii_uppercase();
+    let bytes = isrc.as_bytes();
+
+    let valid = bytes.len() == 12
+        && bytes[..2].iter().all(u8::is_ascii_uppercase)
+        && bytes[2..5].iter().all(u8::is_ascii_alphanumeric)
+        && bytes[5..].iter().all(u8::is_ascii_digit);
+    valid.then_some(isrc)
+}
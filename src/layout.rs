@@ -0,0 +1,97 @@
+use std::str::FromStr;
+
+use crate::cuesheet::{CueFile, CueIndex, CueTime, CueTrack};
+
+/// How the audio of a medium is split into files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// One file for the whole medium.
+    Image,
+    /// One file per track starting at its INDEX 01; gaps are not marked.
+    Tracks,
+    /// One file per track, with the gap before a track appended to the previous file and marked by an INDEX 00
+    /// in it, as written by EAC's "noncompliant" cuesheets.
+    TracksGapsAppended,
+    /// One file per track, with the gap before a track at the start of its own file.
+    TracksGapsPrepended,
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "image" => Ok(Self::Image),
+            "tracks" => Ok(Self::Tracks),
+            "tracks-gaps-appended" => Ok(Self::TracksGapsAppended),
+            "tracks-gaps-prepended" => Ok(Self::TracksGapsPrepended),
+            _ => Err(format!("invalid layout \"{s}\", expected one of image, tracks, tracks-gaps-appended, tracks-gaps-prepended")),
+        }
+    }
+}
+
+fn index_time(track: &CueTrack, number: u32) -> Option<CueTime> {
+    track.indexes.iter().find(|i| i.number == number).map(|i| i.time)
+}
+
+/// The position of `index` in a file starting at `file_start`, or an error if it comes before the file, which
+/// happens with INDEX positions that are out of order.
+fn relative(index: &CueIndex, track: &CueTrack, file_start: CueTime) -> Result<CueIndex, String> {
+    match index.time.frames.checked_sub(file_start.frames) {
+        Some(frames) => Ok(CueIndex {
+            number: index.number,
+            time: CueTime::from_frames(frames),
+        }),
+        None => Err(format!(
+            "INDEX {:02} of track {:02} at {} comes before the start of its file at {file_start}",
+            index.number, track.number, index.time
+        )),
+    }
+}
+
+/// Splits the single file of a disc image into the files of `layout`, naming each file with `file_name`. Fails if
+/// the INDEX positions are out of order.
+pub fn split_image(image: CueFile, layout: Layout, mut file_name: impl FnMut(&CueTrack) -> String) -> Result<Vec<CueFile>, String> {
+    if layout == Layout::Image {
+        return Ok(vec![image]);
+    }
+
+    let mut files: Vec<CueFile> = Vec::new();
+    let mut previous_file_start = CueTime::default();
+
+    for (i, track) in image.tracks.into_iter().enumerate() {
+        let index_01 = index_time(&track, 1).unwrap_or_default();
+        let index_00 = index_time(&track, 0);
+        let file_start = match layout {
+            Layout::TracksGapsAppended if i == 0 => index_00.unwrap_or(index_01),
+            Layout::TracksGapsPrepended => index_00.unwrap_or(index_01),
+            _ => index_01,
+        };
+
+        let mut file = CueFile {
+            name: file_name(&track),
+            file_type: image.file_type.clone(),
+            leading_indexes: Vec::new(),
+            tracks: Vec::new(),
+        };
+        let indexes =
+            track.indexes.iter().filter(|i| i.time >= file_start).map(|i| relative(i, &track, file_start)).collect::<Result<_, _>>()?;
+        let gap = track.indexes.iter().find(|i| i.time < file_start);
+
+        match (layout, gap, files.last_mut()) {
+            (Layout::TracksGapsAppended, Some(gap), Some(previous_file)) => {
+                previous_file.tracks.push(CueTrack {
+                    indexes: vec![relative(gap, &track, previous_file_start)?],
+                    ..track
+                });
+                file.leading_indexes = indexes;
+            }
+            _ => file.tracks.push(CueTrack { indexes, ..track }),
+        }
+
+        files.push(file);
+        previous_file_start = file_start;
+    }
+
+    Ok(files)
+}
@@ -0,0 +1,31 @@
+pub mod artist_name;
+pub mod barcode;
+pub mod builder;
+pub mod cover_art;
+pub mod credits;
+pub mod cuesheet;
+pub mod date;
+pub mod discid;
+pub mod encoding;
+pub mod escape;
+pub mod genre;
+pub mod isrc;
+pub mod layout;
+pub mod merge;
+pub mod musicbrainz;
+pub mod parser;
+pub mod pseudo_release;
+pub mod sanitize;
+pub mod serializer;
+pub mod template;
+pub mod toc;
+
+pub use builder::{CueSheetBuilder, MediumCueSheet};
+pub use cuesheet::{CueFile, CueIndex, CueSheet, CueTime, CueTrack, FileType, Rem};
+pub use discid::DiscIds;
+pub use layout::Layout;
+pub use merge::{merge, MergeError};
+pub use parser::{parse, ParseError};
+pub use serializer::{serialize, serialize_escaped};
+pub use template::Template;
+pub use toc::Toc;
@@ -1,22 +1,208 @@
-use std::fmt::Write;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
 
-use chrono::Datelike;
-use clap::Parser;
-use if_chain::if_chain;
-use musicbrainz_rs::entity::artist_credit::ArtistCredit;
-use musicbrainz_rs::entity::release::Release;
-use musicbrainz_rs::entity::CoverartResponse;
-use musicbrainz_rs::{Fetch, FetchCoverart};
-
-const USER_AGENT: &str = "musicbrainz_cuesheet/0.1.0 (testing)";
-const COVER_ART_PATH_COMPONENT: &str = "Cover";
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand};
+use musicbrainz_cuesheet::artist_name::ArtistName;
+use musicbrainz_cuesheet::builder::{
+    check_name_collisions, DateSource, DEFAULT_CREDIT_SEPARATOR, DEFAULT_FILE_TEMPLATE, DEFAULT_OUTPUT_TEMPLATE,
+    DEFAULT_TRACK_FILE_TEMPLATE, FILE_TEMPLATE_FIELDS, TRACK_FILE_TEMPLATE_FIELDS,
+};
+use musicbrainz_cuesheet::cover_art::download_cover_art;
+use musicbrainz_cuesheet::encoding::{decode, transliterate_cue_sheet, TextEncoding};
+use musicbrainz_cuesheet::escape::{escape_cue_sheet, QuotePolicy};
+use musicbrainz_cuesheet::genre::{GenreOptions, GenreSource};
+use musicbrainz_cuesheet::merge::merge_release;
+use musicbrainz_cuesheet::musicbrainz::{
+    create_client, fetch_latin_pseudo_release, fetch_release, lookup_discid, lookup_toc, DiscMatch, FetchedRelease,
+};
+use musicbrainz_cuesheet::pseudo_release::Script;
+use musicbrainz_cuesheet::{parse, serialize_escaped, CueSheet, CueSheetBuilder, CueTime, DiscIds, FileType, Layout, Template, Toc};
+use musicbrainz_rs::MusicBrainzClient;
 
 #[derive(Parser)]
 struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+impl Args {
+    /// Parses the command line, running the generate command if no command is given, as before there were others.
+    fn parse_with_default_command() -> Self {
+        let mut args = std::env::args_os().collect::<Vec<_>>();
+        let is_command = args.get(1).and_then(|a| a.to_str()).is_some_and(|a| {
+            matches!(a, "help" | "-h" | "--help") || Self::command().find_subcommand(a).is_some()
+        });
+        if args.len() > 1 && !is_command {
+            args.insert(1, "generate".into());
+        }
+        Self::parse_from(args)
+    }
+}
+
+/// Options of the commands that build cuesheets from a release.
+#[derive(clap::Args)]
+struct MetadataArgs {
+    /// Separator between multiple SONGWRITER, COMPOSER and LYRICIST credits
+    #[clap(long, default_value = DEFAULT_CREDIT_SEPARATOR)]
+    credit_separator: String,
+
+    /// Describe tracks by work and movement, name conductor, orchestra and soloists, and use the composers as album
+    /// PERFORMER
+    #[clap(long)]
+    classical: bool,
+
+    /// Don't append the medium to the TITLE of multi-disc releases, e.g. "- CD 01: Side One"
+    #[clap(long)]
+    no_title_suffix: bool,
+
+    /// Date written as REM DATE: release, release-group (the original release) or both (the release, with the original
+    /// release as REM ORIGINALDATE)
+    #[clap(long, default_value = "release-group")]
+    date_source: DateSource,
+
+    /// Text written as REM COMMENT
+    #[clap(long)]
+    comment: Option<String>,
+
+    /// Where REM GENRE comes from: release-group, release, artist or tags
+    #[clap(long, default_value = "release-group")]
+    genre_source: GenreSource,
+
+    /// Skip genres with fewer votes
+    #[clap(long, default_value_t = 0)]
+    genre_min_votes: u32,
+
+    /// Write at most this many genres, the ones with the most votes
+    #[clap(long)]
+    genre_limit: Option<usize>,
+
+    /// File listing the allowed genres, one per line, or "name = canonical name" to rename a genre
+    #[clap(long)]
+    genre_map: Option<PathBuf>,
+
+    /// Name written for artists: credited, canonical, sort, or alias:<locale> like alias:ja
+    #[clap(long, default_value = "credited")]
+    artist_name: ArtistName,
+
+    /// Only write a track PERFORMER if it differs from the release artist, except on "Various Artists" compilations
+    #[clap(long)]
+    omit_album_performer: bool,
+
+    /// Credit tracks with the artists of their recordings instead of the artists printed on the release
+    #[clap(long)]
+    recording_artists: bool,
+
+    /// Titles to write for releases with a Latin-script pseudo-release: original, latin, or both (the original titles
+    /// plus REM TITLE_LATIN)
+    #[clap(long, default_value = "original")]
+    script: Script,
+}
+
+impl MetadataArgs {
+    fn genre_options(&self) -> GenreOptions {
+        let map = self.genre_map.as_ref().map(|path| {
+            std::fs::read_to_string(path).unwrap().parse().unwrap_or_else(|err| {
+                eprintln!("Invalid genre map {}: {err}", path.display());
+                std::process::exit(1);
+            })
+        });
+
+        GenreOptions {
+            source: self.genre_source,
+            min_votes: self.genre_min_votes,
+            limit: self.genre_limit,
+            map,
+        }
+    }
+
+    /// Fetches the release, along with its Latin-script pseudo-release if its titles are needed.
+    fn fetch(&self, client: &MusicBrainzClient, release_id: &str) -> FetchedRelease {
+        let mut release = fetch_release(client, release_id).unwrap();
+        if self.script != Script::Original {
+            release.latin_pseudo_release = fetch_latin_pseudo_release(client, &release.release).unwrap();
+            if release.latin_pseudo_release.is_none() {
+                eprintln!("The release has no Latin-script pseudo-release, writing the original titles");
+            }
+        }
+        release
+    }
+
+    fn builder<'a>(&self, release: &'a FetchedRelease) -> CueSheetBuilder<'a> {
+        let mut builder = CueSheetBuilder::new(&release.release)
+            .artist_aliases(&release.artist_aliases)
+            .credit_separator(self.credit_separator.as_str())
+            .classical(self.classical)
+            .title_suffix(!self.no_title_suffix)
+            .date_source(self.date_source)
+            .genres(self.genre_options())
+            .artist_name(self.artist_name.clone())
+            .omit_album_performer(self.omit_album_performer)
+            .recording_artists(self.recording_artists)
+            .script(self.script);
+        if let Some(pseudo_release) = &release.latin_pseudo_release {
+            builder = builder.pseudo_release(pseudo_release);
+        }
+        if let Some(comment) = &self.comment {
+            builder = builder.comment(comment.as_str());
+        }
+        builder
+    }
+}
+
+/// Options of the commands that write cuesheets.
+#[derive(clap::Args)]
+struct OutputArgs {
+    /// How double quotes inside titles and names are written: typographic, single or strip
+    #[clap(long, default_value = "typographic")]
+    quotes: QuotePolicy,
+
+    /// Text encoding of the cuesheet: utf8, utf8-bom, windows-1252, shift-jis or latin1; characters that cannot be
+    /// represented are transliterated
+    #[clap(long, default_value = "utf8")]
+    encoding: TextEncoding,
+}
+
+/// Options of the generate command, which can also be given without the command name.
+#[derive(clap::Args)]
+#[clap(group(ArgGroup::new("source").required(true).args(["release_id", "discid", "toc"])))]
+struct GenerateArgs {
     #[clap(short = 'r', long)]
-    release_id: String,
+    release_id: Option<String>,
+
+    /// MusicBrainz disc ID of the medium
+    #[clap(short = 'd', long)]
+    discid: Option<String>,
+
+    /// Raw TOC of the medium: first track, last track, lead-out offset and track offsets, e.g. "1 12 198592 150 ..."
+    #[clap(short = 't', long)]
+    toc: Option<Toc>,
+
+    /// Name of the audio FILE, with placeholders {artist}, {album}, {medium}, {medium_title}, {format}, {discs}
+    /// and {catalog}; numbers can be zero-padded like {medium:02}
+    #[clap(short = 'f', long, default_value = DEFAULT_FILE_TEMPLATE)]
+    file_template: Template,
+
+    /// Type of the audio FILE: WAVE, MP3, AIFF, BINARY or MOTOROLA
+    #[clap(long, default_value = "WAVE")]
+    file_type: FileType,
+
+    /// How the audio is split into files: image or tracks; the gap layouts need the gaps of a rip, see merge
+    #[clap(short = 'l', long, default_value = "image")]
+    layout: Layout,
+
+    /// Name of the per-track FILEs of the track layouts, with the placeholders of --file-template plus {track},
+    /// {title} and {performer}
+    #[clap(long, default_value = DEFAULT_TRACK_FILE_TEMPLATE)]
+    track_file_template: Template,
+
+    /// Name of the written cuesheets without the ".cue" extension, with the placeholders of --file-template
+    #[clap(long, default_value = DEFAULT_OUTPUT_TEMPLATE)]
+    output_template: Template,
+
+    #[clap(flatten)]
+    metadata: MetadataArgs,
+
+    #[clap(flatten)]
+    output: OutputArgs,
 
     #[clap(short = 'c', long)]
     cover_art: bool,
@@ -25,151 +211,219 @@ struct Args {
     out_dir: PathBuf,
 }
 
-fn join_artists(artists: &[ArtistCredit]) -> String {
-    artists
-        .iter()
-        .map(|a| format!("{}{}", a.name, a.joinphrase.clone().unwrap_or_default()))
-        .collect::<String>()
-}
+// parsed once, so the size of the largest variant does not matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand)]
+enum Command {
+    /// Generate one cuesheet per medium of a release, or for the medium matching a disc ID or TOC; this is the default
+    /// when no command is given
+    Generate(GenerateArgs),
+
+    /// Merge release metadata into an existing cuesheet, keeping its FILE, INDEX, PREGAP and FLAGS lines unless
+    /// --layout splits its FILE
+    Merge {
+        #[clap(short = 'r', long)]
+        release_id: String,
+
+        #[clap(short = 'i', long)]
+        input: PathBuf,
+
+        /// Position of the medium to merge, if the release has several media with the same number of tracks
+        #[clap(short = 'm', long)]
+        medium: Option<u32>,
+
+        /// How the audio of a single-file cuesheet is split into files: image, tracks, tracks-gaps-appended or
+        /// tracks-gaps-prepended
+        #[clap(short = 'l', long, default_value = "image")]
+        layout: Layout,
+
+        /// Name of the per-track FILEs of the track layouts, with the placeholders of generate's --track-file-template
+        #[clap(long, default_value = DEFAULT_TRACK_FILE_TEMPLATE)]
+        track_file_template: Template,
+
+        #[clap(flatten)]
+        metadata: MetadataArgs,
+
+        #[clap(flatten)]
+        output: OutputArgs,
 
-fn millisecond_to_mmssff(ms: u32) -> String {
-    // From https://wiki.hydrogenaud.io/index.php?title=Cue_sheet:
-    // FF the number of frames (there are seventy five frames to one second)
-    const MILLISECONDS_PER_FRAME: f64 = 1000.0 / 75.0;
+        #[clap(short = 'o', long)]
+        out_dir: PathBuf,
+    },
 
-    let ms_part = (ms as f64) % 1000.0;
-    let frames = (ms_part / MILLISECONDS_PER_FRAME).round() as u32;
-    let seconds = (ms / 1000) % 60;
-    let minutes = ms / 60000;
+    /// Compute the MusicBrainz disc ID, FreeDB ID and AccurateRip IDs of a single-file cuesheet or a raw TOC
+    #[clap(group(ArgGroup::new("source").required(true).args(["input", "toc"])))]
+    Ids {
+        #[clap(short = 'i', long, requires = "length")]
+        input: Option<PathBuf>,
 
-    format!("{minutes:02}:{seconds:02}:{frames:02}")
+        /// Length of the cuesheet's audio file as MM:SS:FF
+        #[clap(short = 'l', long)]
+        length: Option<CueTime>,
+
+        #[clap(short = 't', long)]
+        toc: Option<Toc>,
+    },
 }
 
-fn download_cover_art(url: &str, output_path_prefix: &Path) {
-    let resp = reqwest::blocking::get(url).unwrap();
-    if resp.status().is_success() {
-        let file_extension = Path::new(resp.url().path()).extension().unwrap().to_string_lossy();
-        let output_path = output_path_prefix.with_extension(&*file_extension);
-        std::fs::write(output_path, resp.bytes().unwrap()).unwrap();
-    } else {
-        eprintln!("HTTP error code {}", resp.status());
+fn pick_disc_match(matches: Vec<DiscMatch>) -> DiscMatch {
+    let mut matches = matches.into_iter();
+    let Some(disc_match) = matches.next() else {
+        eprintln!("No release found for the disc");
+        std::process::exit(1);
+    };
+
+    for other in matches {
+        eprintln!("Also matching: release {} medium {}", other.release_id, other.medium_position);
     }
+    disc_match
 }
 
-fn main() {
-    let args = Args::parse();
-    std::fs::create_dir_all(&args.out_dir).unwrap();
-
-    musicbrainz_rs::config::set_user_agent(USER_AGENT);
-
-    let release = Release::fetch()
-        .id(&args.release_id)
-        .with_artist_credits()
-        .with_genres()
-        .with_labels()
-        .with_recordings()
-        .with_release_groups()
-        .execute()
-        .unwrap();
-    let mut release_cuesheet = String::new();
-    //std::fs::write("D:\\mb_debug.txt", format!("{:#?}", release));
-
-    if let Some(artists) = release.artist_credit {
-        writeln!(release_cuesheet, "PERFORMER \"{}\"", join_artists(&artists)).unwrap();
+/// Reads and parses a cuesheet in any of the encodings rippers write, exiting with the error if it cannot.
+fn read_cue_sheet(path: &Path) -> CueSheet {
+    let parsed = std::fs::read(path).map_err(|err| err.to_string()).and_then(|bytes| parse(&decode(&bytes)).map_err(|err| err.to_string()));
+    parsed.unwrap_or_else(|err| {
+        eprintln!("Cannot read {}: {err}", path.display());
+        std::process::exit(1);
+    })
+}
+
+/// Escapes and encodes the cuesheet and writes it to `path`, warning about every field that was changed.
+fn write_cue_sheet(mut cue_sheet: CueSheet, path: &Path, output: &OutputArgs) {
+    let file_name = path.file_name().unwrap().to_string_lossy();
+    for field in escape_cue_sheet(&mut cue_sheet, output.quotes) {
+        eprintln!("{file_name}: {} changed from {:?} to {:?}", field.field, field.original, field.rewritten);
+    }
+    let lossy = transliterate_cue_sheet(&mut cue_sheet, output.encoding).unwrap_or_else(|err| {
+        eprintln!("Cannot write {}: {err}, choose another --encoding", path.display());
+        std::process::exit(1);
+    });
+    for field in lossy {
+        eprintln!("{file_name}: {} lost information: {:?} written as {:?}", field.field, field.original, field.rewritten);
     }
 
-    if let Some(release_group) = release.release_group {
-        if let Some(genres) = release_group.genres {
-            writeln!(release_cuesheet, "REM GENRE {}", genres.into_iter().map(|g| g.name).collect::<Vec<_>>().join("; ")).unwrap();
-        }
+    std::fs::write(path, output.encoding.encode(&serialize_escaped(&cue_sheet, output.quotes))).unwrap();
+}
 
-        if let Some(release_date) = release_group.first_release_date {
-            // currently musicbrainz_rs always converts incomplete date (e.g. without month and/or day) to NaiveDate,
-            // filling the missing part with value "01"
-            writeln!(
-                release_cuesheet,
-                "REM DATE {}",
-                if release_date.ordinal() == 1 {
-                    release_date.year().to_string()
-                } else {
-                    release_date.to_string()
-                }
-            )
-            .unwrap();
-        }
-    }
+fn generate(client: &MusicBrainzClient, args: GenerateArgs) {
+    let GenerateArgs {
+        release_id,
+        discid,
+        toc,
+        file_template,
+        file_type,
+        layout,
+        track_file_template,
+        output_template,
+        metadata,
+        output,
+        cover_art,
+        out_dir,
+    } = args;
 
-    if let Some(label) = release.label_info {
-        for l in label {
-            if let Some(li) = l.label {
-                if !li.name.is_empty() {
-                    writeln!(release_cuesheet, "REM COMMENT \"{}\"", li.name).unwrap();
-                }
-            }
-        }
+    if let Err(err) = file_template
+        .validate(FILE_TEMPLATE_FIELDS)
+        .and_then(|_| track_file_template.validate(TRACK_FILE_TEMPLATE_FIELDS))
+        .and_then(|_| output_template.validate(FILE_TEMPLATE_FIELDS))
+    {
+        eprintln!("Invalid file template: {err}");
+        std::process::exit(1);
+    }
+    if matches!(layout, Layout::TracksGapsAppended | Layout::TracksGapsPrepended) {
+        eprintln!("Invalid layout: generated cuesheets have no gaps, split a rip with merge --layout instead");
+        std::process::exit(1);
     }
 
-    writeln!(release_cuesheet, "REM MUSICBRAINZ_ALBUM_ID {}", release.id).unwrap();
-    writeln!(release_cuesheet, "FILE \"CDImage.flac\" WAVE").unwrap();
+    let disc_match = match (&discid, toc) {
+        (Some(discid), _) => Some(pick_disc_match(lookup_discid(client, discid).unwrap())),
+        (None, Some(toc)) => Some(pick_disc_match(lookup_toc(client, &toc).unwrap())),
+        (None, None) => None,
+    };
+    let release_id = release_id.unwrap_or_else(|| disc_match.as_ref().unwrap().release_id.clone());
 
-    if let Some(media) = release.media {
-        let is_album = media.len() > 1;
-        for medium in media {
-            let medium_id = format!("{} {:02}", medium.format.unwrap_or_default(), medium.position.unwrap_or_default());
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let release = metadata.fetch(client, &release_id);
 
-            let mut medium_title = format!("TITLE \"{}", release.title);
-            if is_album {
-                medium_title += &format!("- {medium_id}")
-            }
-            if_chain! {
-                if let Some(t) = medium.title;
-                if !t.is_empty();
-                then {
-                    medium_title += &format!(": {t}");
-                }
-            }
-            medium_title += "\"";
+    let mut builder = metadata
+        .builder(&release)
+        .file_template(file_template)
+        .file_type(file_type)
+        .layout(layout)
+        .track_file_template(track_file_template)
+        .output_template(output_template);
+    if let Some(disc_match) = disc_match {
+        builder = builder.medium(disc_match.medium_position).toc(disc_match.toc);
+    }
 
-            let mut medium_cuesheet = format!("{medium_title}\n{release_cuesheet}");
+    let media = builder.build();
+    if let Err(err) = check_name_collisions(&media) {
+        eprintln!("Invalid output template: {err}");
+        std::process::exit(1);
+    }
 
-            let mut track_start = 0;
-            for track in medium.tracks.unwrap_or_default() {
-                writeln!(medium_cuesheet, "  TRACK {:02} AUDIO", track.position).unwrap();
-                writeln!(medium_cuesheet, "    TITLE \"{}\"", track.title).unwrap();
+    for medium in media {
+        let output_filename = format!("{}.cue", medium.name);
+        if let Some(track) = medium.cue_sheet.tracks().find(|t| t.indexes.is_empty()) {
+            eprintln!("{output_filename}: no INDEX from track {} on, as MusicBrainz has no length for a track before it", track.number);
+        }
+        write_cue_sheet(medium.cue_sheet, &out_dir.join(output_filename), &output);
+    }
 
-                if let Some(track_artists) = &track.recording.artist_credit {
-                    writeln!(medium_cuesheet, "    PERFORMER \"{}\"", join_artists(track_artists)).unwrap();
-                }
+    if cover_art {
+        download_cover_art(client, &release_id, &out_dir);
+    }
+}
+
+fn main() {
+    let args = Args::parse_with_default_command();
+    let client = create_client();
 
-                let track_length = track.length.unwrap();
-                writeln!(medium_cuesheet, "    INDEX 01 {}", millisecond_to_mmssff(track_start)).unwrap();
-                track_start += track_length;
+    match args.command {
+        Command::Generate(args) => generate(&client, args),
+        Command::Merge {
+            release_id,
+            input,
+            medium,
+            layout,
+            track_file_template,
+            metadata,
+            output,
+            out_dir,
+        } => {
+            if let Err(err) = track_file_template.validate(TRACK_FILE_TEMPLATE_FIELDS) {
+                eprintln!("Invalid file template: {err}");
+                std::process::exit(1);
             }
 
-            let output_filename = format!("{medium_id}.cue");
-            std::fs::write(args.out_dir.join(output_filename), medium_cuesheet).unwrap();
-        }
-    }
+            let rip = read_cue_sheet(&input);
+            let release = metadata.fetch(&client, &release_id);
+            let builder = metadata.builder(&release).layout(layout).track_file_template(track_file_template);
 
-    if args.cover_art {
-        let cover_art_path = args.out_dir.join(COVER_ART_PATH_COMPONENT);
-        if let Ok(resp) = Release::fetch_coverart().id(&args.release_id).execute() {
-            match resp {
-                CoverartResponse::Url(cover_art_url) => {
-                    download_cover_art(&cover_art_url, &cover_art_path);
+            match merge_release(&builder, &rip, medium) {
+                Ok(merged) => {
+                    std::fs::create_dir_all(&out_dir).unwrap();
+                    write_cue_sheet(merged, &out_dir.join(input.file_name().unwrap()), &output);
                 }
-                CoverartResponse::Json(cover_art) => {
-                    std::fs::create_dir_all(&cover_art_path).unwrap();
-
-                    for img in cover_art.images {
-                        let img_filename_stem = img.types.iter().map(|t| format!("{t:#?}")).collect::<Vec<_>>().join("_");
-                        download_cover_art(&img.image, &cover_art_path.join(img_filename_stem));
-                        std::thread::sleep(Duration::from_secs(1));
-                    }
+                Err(err) => {
+                    eprintln!("Refusing to merge {}: {err}", input.display());
+                    std::process::exit(1);
                 }
             }
-        } else {
-            eprintln!("Failed to download cover art")
+        }
+        Command::Ids { input, length, toc } => {
+            let toc = toc.unwrap_or_else(|| {
+                let cue_sheet = read_cue_sheet(&input.unwrap());
+                Toc::from_cue_sheet(&cue_sheet, length.unwrap()).unwrap_or_else(|err| {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                })
+            });
+
+            let disc_ids = DiscIds::new(&toc);
+            println!("TOC: {toc}");
+            println!("MusicBrainz disc ID: {}", disc_ids.musicbrainz);
+            println!("FreeDB ID: {}", disc_ids.freedb_string());
+            println!("AccurateRip: {}", disc_ids.accuraterip_string());
         }
     }
 }
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use crate::builder::{CueSheetBuilder, MediumCueSheet, INDEX_SOURCE_REM};
+use crate::cuesheet::{CueSheet, Rem};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    TrackCountMismatch { rip: usize, release: usize },
+    MediumNotFound(u32),
+    AmbiguousMedium,
+    Split(String),
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TrackCountMismatch { rip, release } => {
+                write!(f, "the cuesheet has {rip} tracks but the release medium has {release}")
+            }
+            Self::MediumNotFound(position) => write!(f, "the release has no medium {position}"),
+            Self::AmbiguousMedium => write!(f, "no single medium matches the cuesheet's track count, select one explicitly"),
+            Self::Split(message) => write!(f, "cannot split the cuesheet: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+// These describe the disc layout, which is kept from the rip
+const LAYOUT_REMS: &[&str] = &[INDEX_SOURCE_REM, "DISCID", "MUSICBRAINZ_DISC_ID"];
+
+/// Replaces the REM entries of `target` with the ones from `source` that have the same name, keeping the rest.
+fn merge_rems(target: &mut Vec<Rem>, source: &[Rem]) {
+    let source = source.iter().filter(|s| !LAYOUT_REMS.contains(&s.name.as_str()));
+    target.retain(|t| !source.clone().any(|s| s.name == t.name));
+    target.extend(source.cloned());
+}
+
+/// Picks the generated medium cuesheet to merge into `rip`, either by its position or as the only medium with
+/// the same number of tracks.
+pub fn select_medium<'a>(media: &'a [MediumCueSheet], rip: &CueSheet, position: Option<u32>) -> Result<&'a MediumCueSheet, MergeError> {
+    if let Some(position) = position {
+        return media.iter().find(|m| m.position == position).ok_or(MergeError::MediumNotFound(position));
+    }
+    if let [medium] = media {
+        return Ok(medium);
+    }
+
+    let track_count = rip.tracks().count();
+    let mut candidates = media.iter().filter(|m| m.cue_sheet.tracks().count() == track_count);
+    match (candidates.next(), candidates.next()) {
+        (Some(medium), None) => Ok(medium),
+        _ => Err(MergeError::AmbiguousMedium),
+    }
+}
+
+/// Merges the metadata of a generated cuesheet into an existing rip cuesheet. Only TITLE, PERFORMER, SONGWRITER and
+/// REM entries are taken from `generated`, plus CATALOG and ISRC if the rip has none; FILE, INDEX, PREGAP, POSTGAP and
+/// FLAGS of `rip` are kept as they are.
+pub fn merge(rip: &CueSheet, generated: &CueSheet) -> Result<CueSheet, MergeError> {
+    let rip_track_count = rip.tracks().count();
+    let generated_track_count = generated.tracks().count();
+    if rip_track_count != generated_track_count {
+        return Err(MergeError::TrackCountMismatch {
+            rip: rip_track_count,
+            release: generated_track_count,
+        });
+    }
+
+    let mut merged = rip.clone();
+    if merged.catalog.is_none() {
+        merged.catalog.clone_from(&generated.catalog);
+    }
+    if generated.title.is_some() {
+        merged.title.clone_from(&generated.title);
+    }
+    if generated.performer.is_some() {
+        merged.performer.clone_from(&generated.performer);
+    }
+    merge_rems(&mut merged.rems, &generated.rems);
+
+    for (track, generated_track) in merged.tracks_mut().zip(generated.tracks()) {
+        if generated_track.title.is_some() {
+            track.title.clone_from(&generated_track.title);
+        }
+        if generated_track.performer.is_some() {
+            track.performer.clone_from(&generated_track.performer);
+        }
+        if generated_track.songwriter.is_some() {
+            track.songwriter.clone_from(&generated_track.songwriter);
+        }
+        if track.isrc.is_none() {
+            track.isrc.clone_from(&generated_track.isrc);
+        }
+        merge_rems(&mut track.rems, &generated_track.rems);
+    }
+
+    Ok(merged)
+}
+
+/// Merges the metadata of a release into `rip`: builds the cuesheets of the release, merges the one of the medium
+/// picked by [`select_medium`] and splits the result into the files of the builder's layout.
+pub fn merge_release(builder: &CueSheetBuilder, rip: &CueSheet, position: Option<u32>) -> Result<CueSheet, MergeError> {
+    let media = builder.build();
+    let medium = select_medium(&media, rip, position)?;
+    let mut merged = merge(rip, &medium.cue_sheet)?;
+    builder.split_rip(&mut merged, medium.position).map_err(MergeError::Split)?;
+    Ok(merged)
+}
@@ -0,0 +1,136 @@
+use musicbrainz_rs::entity::BrowseResult;
+use musicbrainz_rs::entity::discid::Discid;
+use musicbrainz_rs::entity::release::Release;
+use musicbrainz_rs::{ApiRequest, Fetch, MusicBrainzClient};
+
+use crate::artist_name::{collect_artist_aliases, ArtistAlias};
+use crate::pseudo_release::{is_latin, translations};
+use crate::toc::Toc;
+
+const USER_AGENT: &str = "musicbrainz_cuesheet/0.1.0 (testing)";
+
+/// A medium found by a disc ID or TOC lookup.
+pub struct DiscMatch {
+    pub release_id: String,
+    pub medium_position: u32,
+    pub toc: Toc,
+}
+
+pub fn create_client() -> MusicBrainzClient {
+    let mut client = MusicBrainzClient::default();
+    client.set_user_agent(USER_AGENT).unwrap();
+    client
+}
+
+/// A release along with the aliases of its credited artists, whose locales `musicbrainz_rs` does not deserialize.
+pub struct FetchedRelease {
+    pub release: Release,
+    pub artist_aliases: Vec<ArtistAlias>,
+    /// The pseudo-release with a Latin-script tracklist, if it was fetched with [`fetch_latin_pseudo_release`].
+    pub latin_pseudo_release: Option<Release>,
+}
+
+/// Fetches a release with everything the cuesheet builder needs.
+pub fn fetch_release(client: &MusicBrainzClient, release_id: &str) -> Result<FetchedRelease, musicbrainz_rs::Error> {
+    let request = Release::fetch()
+        .id(release_id)
+        .with_aliases()
+        // along with the recordings, this includes the artist credits of both the tracks and the recordings
+        .with_artist_credits()
+        .with_discids()
+        .with_genres()
+        .with_isrcs()
+        .with_labels()
+        .with_recordings()
+        .with_release_groups()
+        .with_tags()
+        .with_recording_level_relations()
+        .with_work_relations()
+        .with_work_level_relations()
+        .with_artist_relations()
+        .with_release_relations()
+        .as_api_request(client);
+    let url = request.url.clone();
+    let json = request.get_json(client)?;
+
+    let mut artist_aliases = Vec::new();
+    collect_artist_aliases(&json, &mut artist_aliases);
+    Ok(FetchedRelease {
+        release: ApiRequest::parse_json(json, &url)?,
+        artist_aliases,
+        latin_pseudo_release: None,
+    })
+}
+
+/// Fetches the tracklist of the pseudo-release transliterating the release into Latin script, if it has one.
+pub fn fetch_latin_pseudo_release(client: &MusicBrainzClient, release: &Release) -> Result<Option<Release>, musicbrainz_rs::Error> {
+    // the linked pseudo-releases usually say which script they are written in, which saves fetching the others
+    for translation in translations(release).filter(|r| is_latin(r) != Some(false)) {
+        let pseudo_release = Release::fetch().id(&translation.id).with_recordings().execute_with_client(client)?;
+        if is_latin(&pseudo_release) == Some(true) {
+            return Ok(Some(pseudo_release));
+        }
+    }
+    Ok(None)
+}
+
+/// The media of `releases` that carry the given MusicBrainz disc ID.
+pub fn discid_matches(releases: &[Release], discid: &str) -> Vec<DiscMatch> {
+    let mut matches = Vec::new();
+    for release in releases {
+        for medium in release.media.iter().flatten() {
+            if let Some(disc) = medium.discs.iter().flatten().find(|d| d.id == discid) {
+                matches.push(DiscMatch {
+                    release_id: release.id.clone(),
+                    medium_position: medium.position.unwrap_or_default(),
+                    toc: Toc::from_disc(disc),
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Finds the media that carry the given MusicBrainz disc ID, or none if MusicBrainz does not know it.
+pub fn lookup_discid(client: &MusicBrainzClient, discid: &str) -> Result<Vec<DiscMatch>, musicbrainz_rs::Error> {
+    match Discid::fetch().id(discid).execute_with_client(client) {
+        Ok(result) => Ok(discid_matches(result.releases.as_deref().unwrap_or_default(), discid)),
+        Err(musicbrainz_rs::Error::NotFound(_)) => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// The media of `releases` matching a raw TOC. Media with a disc ID of exactly that TOC come before media that
+/// merely have the same number of tracks.
+pub fn toc_matches(releases: &[Release], toc: &Toc) -> Vec<DiscMatch> {
+    let track_count = toc.offsets.len() as u32;
+    let mut exact_matches = Vec::new();
+    let mut matches = Vec::new();
+    for release in releases {
+        for medium in release.media.iter().flatten() {
+            let m = DiscMatch {
+                release_id: release.id.clone(),
+                medium_position: medium.position.unwrap_or_default(),
+                toc: toc.clone(),
+            };
+            if medium.discs.iter().flatten().any(|d| toc.matches(d)) {
+                exact_matches.push(m);
+            } else if medium.track_count == track_count {
+                matches.push(m);
+            }
+        }
+    }
+
+    exact_matches.append(&mut matches);
+    exact_matches
+}
+
+/// Finds the media matching a raw TOC through the fuzzy TOC lookup, see [`toc_matches`].
+pub fn lookup_toc(client: &MusicBrainzClient, toc: &Toc) -> Result<Vec<DiscMatch>, musicbrainz_rs::Error> {
+    let mut request = Discid::fetch().id("-").as_api_request(client);
+    request.url.push_str(&format!("&toc={}", toc.to_query()));
+    let url = request.url.clone();
+    let json = request.get_json(client)?;
+    let result: BrowseResult<Release> = ApiRequest::parse_json(json, &url)?;
+    Ok(toc_matches(&result.entities, toc))
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::cuesheet::{CueFile, CueIndex, CueSheet, CueTime, CueTrack, FileType, Rem};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a possibly quoted string argument. Everything between the first and the last quote is taken as is,
+/// so that stray quotes inside a value don't truncate it.
+fn parse_string(s: &str) -> (String, bool) {
+    let s = s.trim();
+    match s.strip_prefix('"') {
+        Some(rest) => (rest.strip_suffix('"').unwrap_or(rest).to_string(), true),
+        None => (s.to_string(), false),
+    }
+}
+
+fn split_file_args(s: &str) -> Option<(String, String)> {
+    let s = s.trim();
+    let (name, file_type) = if s.starts_with('"') {
+        let end = s.rfind('"').filter(|&i| i > 0)?;
+        (&s[1..end], &s[end + 1..])
+    } else {
+        s.rsplit_once(char::is_whitespace)?
+    };
+
+    let file_type = file_type.trim();
+    (!file_type.is_empty()).then(|| (name.to_string(), file_type.to_string()))
+}
+
+struct Parser {
+    cue_sheet: CueSheet,
+    in_track: bool,
+}
+
+impl Parser {
+    fn current_file(&mut self) -> Option<&mut CueFile> {
+        self.cue_sheet.files.last_mut()
+    }
+
+    fn current_track(&mut self) -> Option<&mut CueTrack> {
+        if self.in_track {
+            self.current_file().and_then(|f| f.tracks.last_mut())
+        } else {
+            None
+        }
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        let (command, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim();
+
+        match command.to_ascii_uppercase().as_str() {
+            "REM" => {
+                let (name, value) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+                let (value, quoted) = parse_string(value);
+                let rem = Rem {
+                    name: name.to_string(),
+                    value,
+                    quoted,
+                };
+                match self.current_track() {
+                    Some(track) => track.rems.push(rem),
+                    None => self.cue_sheet.rems.push(rem),
+                }
+            }
+            "CATALOG" => self.cue_sheet.catalog = Some(args.to_string()),
+            "CDTEXTFILE" => self.cue_sheet.cdtextfile = Some(parse_string(args).0),
+            "TITLE" | "PERFORMER" | "SONGWRITER" => {
+                let value = Some(parse_string(args).0);
+                let field = match (self.in_track, command.to_ascii_uppercase().as_str()) {
+                    (true, "TITLE") => &mut self.current_track().unwrap().title,
+                    (true, "PERFORMER") => &mut self.current_track().unwrap().performer,
+                    (true, _) => &mut self.current_track().unwrap().songwriter,
+                    (false, "TITLE") => &mut self.cue_sheet.title,
+                    (false, "PERFORMER") => &mut self.cue_sheet.performer,
+                    (false, _) => &mut self.cue_sheet.songwriter,
+                };
+                *field = value;
+            }
+            "FILE" => {
+                let (name, file_type) = split_file_args(args).ok_or("FILE requires a file name and a type")?;
+                self.cue_sheet.files.push(CueFile {
+                    name,
+                    file_type: file_type.parse().unwrap_or(FileType::Other(file_type)),
+                    leading_indexes: Vec::new(),
+                    tracks: Vec::new(),
+                });
+                self.in_track = false;
+            }
+            "TRACK" => {
+                let (number, data_type) = args.split_once(char::is_whitespace).ok_or("TRACK requires a number and a type")?;
+                let number = number.parse().map_err(|_| format!("invalid track number \"{number}\""))?;
+                let file = self.current_file().ok_or("TRACK before any FILE")?;
+                file.tracks.push(CueTrack {
+                    data_type: data_type.trim().to_string(),
+                    ..CueTrack::audio(number)
+                });
+                self.in_track = true;
+            }
+            "INDEX" => {
+                let (number, time) = args.split_once(char::is_whitespace).ok_or("INDEX requires a number and a time")?;
+                let index = CueIndex {
+                    number: number.parse().map_err(|_| format!("invalid index number \"{number}\""))?,
+                    time: time.trim().parse()?,
+                };
+                if let Some(track) = self.current_track() {
+                    track.indexes.push(index);
+                } else if self.cue_sheet.tracks().next().is_some() {
+                    self.current_file().unwrap().leading_indexes.push(index);
+                } else {
+                    return Err("INDEX before any TRACK".to_string());
+                }
+            }
+            "PREGAP" | "POSTGAP" => {
+                let time = Some(args.parse::<CueTime>()?);
+                let track = self.current_track().ok_or_else(|| format!("{command} outside of a TRACK"))?;
+                if command.eq_ignore_ascii_case("PREGAP") {
+                    track.pregap = time;
+                } else {
+                    track.postgap = time;
+                }
+            }
+            "FLAGS" => {
+                let track = self.current_track().ok_or("FLAGS outside of a TRACK")?;
+                track.flags = args.split_whitespace().map(str::to_string).collect();
+            }
+            "ISRC" => {
+                let track = self.current_track().ok_or("ISRC outside of a TRACK")?;
+                track.isrc = Some(args.to_string());
+            }
+            _ => return Err(format!("unknown command \"{command}\"")),
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the text of a cuesheet, e.g. one written by EAC, XLD or [`crate::serialize`].
+pub fn parse(input: &str) -> Result<CueSheet, ParseError> {
+    let mut parser = Parser {
+        cue_sheet: CueSheet::default(),
+        in_track: false,
+    };
+
+    for (i, line) in input.trim_start_matches('\u{feff}').lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        parser.parse_line(line).map_err(|message| ParseError { line: i + 1, message })?;
+    }
+
+    Ok(parser.cue_sheet)
+}
@@ -0,0 +1,48 @@
+use std::str::FromStr;
+
+use musicbrainz_rs::entity::relations::RelationContent;
+use musicbrainz_rs::entity::release::{Release, ReleaseScript};
+
+/// Type of the release relationship from a release to a pseudo-release with a translated or transliterated tracklist.
+pub const TRANSLATION_RELATION: &str = "transl-tracklisting";
+
+/// Which script the titles are written in, given a pseudo-release with a Latin-script tracklist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    /// The titles of the release itself.
+    Original,
+    /// The titles of the pseudo-release.
+    Latin,
+    /// The titles of the release, along with the ones of the pseudo-release as REM TITLE_LATIN.
+    Both,
+}
+
+impl FromStr for Script {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "original" => Ok(Self::Original),
+            "latin" => Ok(Self::Latin),
+            "both" => Ok(Self::Both),
+            _ => Err(format!("invalid script \"{s}\", expected one of original, latin, both")),
+        }
+    }
+}
+
+/// The pseudo-releases with a translated or transliterated tracklist of the release, as linked by its release
+/// relationships. These only hold the basic release fields, not the tracklist.
+pub fn translations(release: &Release) -> impl Iterator<Item = &Release> {
+    release.relations.iter().flatten().filter_map(|r| match &r.content {
+        RelationContent::Release(target) if r.relation_type == TRANSLATION_RELATION && r.direction == "forward" => {
+            Some(target.as_ref())
+        }
+        _ => None,
+    })
+}
+
+/// Whether the tracklist of the release is written in Latin script, or `None` if its script is not known.
+pub fn is_latin(release: &Release) -> Option<bool> {
+    let script = release.text_representation.as_ref()?.script.as_ref()?;
+    Some(*script == ReleaseScript::Latn)
+}
@@ -0,0 +1,27 @@
+// Characters that are not allowed in file names on Windows or NTFS shares, see
+// https://learn.microsoft.com/en-us/windows/win32/fileio/naming-a-file
+const RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4",
+    "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Makes `name` usable as a file name on every platform: reserved and control characters are replaced with "_",
+/// trailing dots and spaces are removed, and reserved device names such as "CON" are prefixed with "_".
+pub fn sanitize_file_name(name: &str) -> String {
+    let mut sanitized = name
+        .chars()
+        .map(|c| if RESERVED_CHARS.contains(&c) || c.is_control() { '_' } else { c })
+        .collect::<String>()
+        .trim_end_matches(['.', ' '])
+        .to_string();
+
+    let stem = sanitized.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem)) {
+        sanitized.insert(0, '_');
+    }
+    if sanitized.is_empty() {
+        sanitized.push('_');
+    }
+    sanitized
+}
@@ -0,0 +1,91 @@
+use std::fmt::Write;
+
+use crate::cuesheet::{CueIndex, CueSheet, Rem};
+use crate::escape::{escape_cue_sheet, QuotePolicy};
+
+fn write_rem(out: &mut String, indent: &str, rem: &Rem) {
+    if rem.quoted {
+        writeln!(out, "{indent}REM {} \"{}\"", rem.name, rem.value).unwrap();
+    } else if rem.value.is_empty() {
+        writeln!(out, "{indent}REM {}", rem.name).unwrap();
+    } else {
+        writeln!(out, "{indent}REM {} {}", rem.name, rem.value).unwrap();
+    }
+}
+
+fn write_index(out: &mut String, index: &CueIndex) {
+    writeln!(out, "    INDEX {:02} {}", index.number, index.time).unwrap();
+}
+
+/// Serializes the cuesheet after escaping its quoted fields and REM values with `policy`, so that a title such as
+/// `12" Single Mix` cannot end its field early. This is how cuesheets should be written.
+pub fn serialize_escaped(cue_sheet: &CueSheet, policy: QuotePolicy) -> String {
+    let mut cue_sheet = cue_sheet.clone();
+    escape_cue_sheet(&mut cue_sheet, policy);
+    serialize(&cue_sheet)
+}
+
+/// Serializes the cuesheet as is. Fields are written verbatim, so they must already have been escaped with
+/// [`escape_cue_sheet`](crate::escape::escape_cue_sheet), otherwise use [`serialize_escaped`].
+pub fn serialize(cue_sheet: &CueSheet) -> String {
+    let mut out = String::new();
+
+    if let Some(catalog) = &cue_sheet.catalog {
+        writeln!(out, "CATALOG {catalog}").unwrap();
+    }
+    if let Some(cdtextfile) = &cue_sheet.cdtextfile {
+        writeln!(out, "CDTEXTFILE \"{cdtextfile}\"").unwrap();
+    }
+    if let Some(title) = &cue_sheet.title {
+        writeln!(out, "TITLE \"{title}\"").unwrap();
+    }
+    if let Some(performer) = &cue_sheet.performer {
+        writeln!(out, "PERFORMER \"{performer}\"").unwrap();
+    }
+    if let Some(songwriter) = &cue_sheet.songwriter {
+        writeln!(out, "SONGWRITER \"{songwriter}\"").unwrap();
+    }
+    for rem in &cue_sheet.rems {
+        write_rem(&mut out, "", rem);
+    }
+
+    for file in &cue_sheet.files {
+        writeln!(out, "FILE \"{}\" {}", file.name, file.file_type).unwrap();
+        for index in &file.leading_indexes {
+            write_index(&mut out, index);
+        }
+
+        for track in &file.tracks {
+            writeln!(out, "  TRACK {:02} {}", track.number, track.data_type).unwrap();
+            if let Some(title) = &track.title {
+                writeln!(out, "    TITLE \"{title}\"").unwrap();
+            }
+            if let Some(performer) = &track.performer {
+                writeln!(out, "    PERFORMER \"{performer}\"").unwrap();
+            }
+            if let Some(songwriter) = &track.songwriter {
+                writeln!(out, "    SONGWRITER \"{songwriter}\"").unwrap();
+            }
+            if let Some(isrc) = &track.isrc {
+                writeln!(out, "    ISRC {isrc}").unwrap();
+            }
+            if !track.flags.is_empty() {
+                writeln!(out, "    FLAGS {}", track.flags.join(" ")).unwrap();
+            }
+            for rem in &track.rems {
+                write_rem(&mut out, "    ", rem);
+            }
+            if let Some(pregap) = track.pregap {
+                writeln!(out, "    PREGAP {pregap}").unwrap();
+            }
+            for index in &track.indexes {
+                write_index(&mut out, index);
+            }
+            if let Some(postgap) = track.postgap {
+                writeln!(out, "    POSTGAP {postgap}").unwrap();
+            }
+        }
+    }
+
+    out
+}
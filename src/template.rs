@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A value to substitute into a template placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    Text(String),
+    Number(u32),
+}
+
+impl From<String> for TemplateValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for TemplateValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<u32> for TemplateValue {
+    fn from(value: u32) -> Self {
+        Self::Number(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder { name: String, width: usize },
+}
+
+/// A file name template with `{name}` placeholders. Numbers can be zero-padded with `{name:02}`, and a literal
+/// brace is written as `{{` or `}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Checks that every placeholder is one of `names`.
+    pub fn validate(&self, names: &[&str]) -> Result<(), String> {
+        for segment in &self.segments {
+            if let Segment::Placeholder { name, .. } = segment {
+                if !names.contains(&name.as_str()) {
+                    return Err(format!("unknown placeholder {{{name}}}, expected one of {}", names.join(", ")));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Substitutes the placeholders. Placeholders without a value are replaced with nothing.
+    pub fn render(&self, values: &[(&str, TemplateValue)]) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(s) => out += s,
+                Segment::Placeholder { name, width } => match values.iter().find(|(n, _)| n == name).map(|(_, v)| v) {
+                    Some(TemplateValue::Text(s)) => out += s,
+                    Some(TemplateValue::Number(n)) => out += &format!("{n:0width$}"),
+                    None => {}
+                },
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err(format!("unclosed '{{' in template \"{s}\"")),
+                        }
+                    }
+                    let (name, width) = match placeholder.split_once(':') {
+                        Some((name, width)) => (name, width.parse().map_err(|_| format!("invalid width in {{{placeholder}}}"))?),
+                        None => (placeholder.as_str(), 0),
+                    };
+                    if name.is_empty() {
+                        return Err(format!("empty placeholder in template \"{s}\""));
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder {
+                        name: name.to_string(),
+                        width,
+                    });
+                }
+                '}' => return Err(format!("unmatched '}}' in template \"{s}\"")),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self {
+            source: s.to_string(),
+            segments,
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::str::FromStr;
+
+use musicbrainz_rs::entity::discid::Disc;
+
+use crate::cuesheet::{CueSheet, CueTime, LEAD_IN_FRAMES};
+
+/// The table of contents of a CD, in the format MusicBrainz uses for its `toc` parameter:
+/// first track number, last track number, lead-out offset and the offset of every track, all offsets in sectors
+/// including the lead-in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toc {
+    pub first_track: u32,
+    pub last_track: u32,
+    pub leadout: u32,
+    pub offsets: Vec<u32>,
+}
+
+impl Toc {
+    pub fn from_disc(disc: &Disc) -> Self {
+        Self {
+            first_track: 1,
+            last_track: disc.offsets.len() as u32,
+            leadout: disc.sectors,
+            offsets: disc.offsets.clone(),
+        }
+    }
+
+    /// Reconstructs the TOC of a cuesheet with a single FILE from its INDEX 01 positions and the length of the
+    /// audio file.
+    pub fn from_cue_sheet(cue_sheet: &CueSheet, length: CueTime) -> Result<Self, String> {
+        let [file] = cue_sheet.files.as_slice() else {
+            return Err("only cuesheets with a single FILE have a known TOC".to_string());
+        };
+        let offsets = file
+            .tracks
+            .iter()
+            .map(|t| {
+                let index = t.indexes.iter().find(|i| i.number == 1).ok_or_else(|| format!("track {} has no INDEX 01", t.number))?;
+                Ok(index.time.frames + LEAD_IN_FRAMES)
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let first_track = file.tracks.first().ok_or("the cuesheet has no tracks")?.number;
+
+        let toc = Self {
+            first_track,
+            last_track: first_track + offsets.len() as u32 - 1,
+            leadout: length.frames + LEAD_IN_FRAMES,
+            offsets,
+        };
+        toc.validate()?;
+        Ok(toc)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.first_track == 0 || self.last_track < self.first_track || self.offsets.len() as u32 != self.last_track - self.first_track + 1 {
+            return Err(format!("TOC for tracks {} to {} has {} offsets", self.first_track, self.last_track, self.offsets.len()));
+        }
+        if self.offsets.windows(2).any(|w| w[0] >= w[1]) || self.offsets.last() >= Some(&self.leadout) {
+            return Err("TOC offsets must be increasing and before the lead-out".to_string());
+        }
+        Ok(())
+    }
+
+    /// INDEX 01 position of every track relative to the start of a disc image.
+    pub fn track_starts(&self) -> Vec<CueTime> {
+        self.offsets.iter().map(|o| CueTime::from_frames(o.saturating_sub(LEAD_IN_FRAMES))).collect()
+    }
+
+    /// Whether `disc` describes exactly this table of contents.
+    pub fn matches(&self, disc: &Disc) -> bool {
+        disc.sectors == self.leadout && disc.offsets == self.offsets
+    }
+
+    /// The value of the `toc` query parameter of the discid lookup endpoint.
+    pub fn to_query(&self) -> String {
+        self.to_string().replace(' ', "+")
+    }
+}
+
+impl fmt::Display for Toc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.first_track, self.last_track, self.leadout)?;
+        for offset in &self.offsets {
+            write!(f, " {offset}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Toc {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = s
+            .split(|c: char| c.is_whitespace() || c == '+')
+            .filter(|n| !n.is_empty())
+            .map(|n| n.parse::<u32>().map_err(|_| format!("invalid TOC number \"{n}\"")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let [first_track, last_track, leadout, offsets @ ..] = numbers.as_slice() else {
+            return Err("TOC needs the first and last track numbers and the lead-out offset".to_string());
+        };
+
+        let toc = Self {
+            first_track: *first_track,
+            last_track: *last_track,
+            leadout: *leadout,
+            offsets: offsets.to_vec(),
+        };
+        toc.validate()?;
+        Ok(toc)
+    }
+}
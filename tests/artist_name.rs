@@ -0,0 +1,53 @@
+use musicbrainz_cuesheet::artist_name::{collect_artist_aliases, ArtistName};
+use musicbrainz_rs::entity::artist_credit::ArtistCredit;
+use serde_json::json;
+
+fn release() -> serde_json::Value {
+    json!({
+        "artist-credit": [
+            {
+                "name": "坂本龍一",
+                "joinphrase": " & ",
+                "artist": {
+                    "id": "6b3f8e5a-4f5e-4d0b-9a6c-9c2b1d3e4f50",
+                    "name": "坂本龍一",
+                    "sort-name": "Sakamoto, Ryuichi",
+                    "aliases": [
+                        { "name": "Ryuichi Sakamoto", "sort-name": "Sakamoto, Ryuichi", "locale": "en", "primary": true },
+                        { "name": "Ryūichi Sakamoto", "sort-name": "Sakamoto, Ryūichi", "locale": "en_GB", "primary": false },
+                        { "name": "Sakamoto", "sort-name": "Sakamoto", "locale": null, "primary": null }
+                    ]
+                }
+            },
+            {
+                "name": "David Sylvian",
+                "joinphrase": "",
+                "artist": { "id": "0e8e9a1a-0b1f-4d8e-9a7c-2d3f4e5a6b70", "name": "David Sylvian", "sort-name": "Sylvian, David" }
+            }
+        ],
+        "media": [{ "tracks": [{ "artist-credit": [] }] }]
+    })
+}
+
+#[test]
+fn names() {
+    let json = release();
+    let credits: Vec<ArtistCredit> = serde_json::from_value(json["artist-credit"].clone()).unwrap();
+    let mut aliases = Vec::new();
+    collect_artist_aliases(&json, &mut aliases);
+    assert_eq!(aliases.len(), 2);
+
+    assert_eq!(ArtistName::Credited.join(&credits, &aliases), "坂本龍一 & David Sylvian");
+    assert_eq!(ArtistName::Sort.join(&credits, &aliases), "Sakamoto, Ryuichi & Sylvian, David");
+    assert_eq!(ArtistName::Alias("en".to_string()).join(&credits, &aliases), "Ryuichi Sakamoto & David Sylvian");
+    assert_eq!(ArtistName::Alias("en_GB".to_string()).join(&credits, &aliases), "Ryūichi Sakamoto & David Sylvian");
+    assert_eq!(ArtistName::Alias("fr".to_string()).join(&credits, &aliases), "坂本龍一 & David Sylvian");
+    assert_eq!(ArtistName::Alias("en_GB".to_string()).join_sort(&credits, &aliases), "Sakamoto, Ryūichi & Sylvian, David");
+}
+
+#[test]
+fn parse() {
+    assert_eq!("canonical".parse(), Ok(ArtistName::Canonical));
+    assert_eq!("alias:ja".parse(), Ok(ArtistName::Alias("ja".to_string())));
+    assert!("alias:".parse::<ArtistName>().is_err());
+}
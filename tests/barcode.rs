@@ -0,0 +1,16 @@
+use musicbrainz_cuesheet::barcode::normalize_barcode;
+
+#[test]
+fn valid() {
+    assert_eq!(normalize_barcode("0720642470527").as_deref(), Some("0720642470527"));
+    assert_eq!(normalize_barcode("720642470527").as_deref(), Some("0720642470527"));
+    assert_eq!(normalize_barcode("5099969945427").as_deref(), Some("5099969945427"));
+}
+
+#[test]
+fn invalid() {
+    assert_eq!(normalize_barcode("0720642470526"), None);
+    assert_eq!(normalize_barcode("07206424705"), None);
+    assert_eq!(normalize_barcode("0 720642 47052"), None);
+    assert_eq!(normalize_barcode(""), None);
+}
@@ -0,0 +1,282 @@
+use musicbrainz_cuesheet::artist_name::ArtistName;
+use musicbrainz_cuesheet::builder::{DateSource, VARIOUS_ARTISTS_ID};
+use musicbrainz_cuesheet::genre::{GenreOptions, GenreSource};
+use musicbrainz_cuesheet::pseudo_release::Script;
+use musicbrainz_cuesheet::{merge, parse, CueSheetBuilder, CueTime, Layout, MediumCueSheet, Rem, Template};
+use musicbrainz_rs::entity::release::Release;
+
+const RELEASE: &str = include_str!("fixtures/release.json");
+
+fn release() -> Release {
+    serde_json::from_str(RELEASE).unwrap()
+}
+
+fn build(builder: impl FnOnce(CueSheetBuilder) -> CueSheetBuilder) -> Vec<MediumCueSheet> {
+    let release = release();
+    builder(CueSheetBuilder::new(&release)).build()
+}
+
+#[test]
+fn catalog_and_isrcs() {
+    let media = build(|b| b);
+    assert_eq!(media[0].cue_sheet.catalog.as_deref(), Some("0077774644624"));
+
+    let tracks = media[0].cue_sheet.tracks().collect::<Vec<_>>();
+    assert_eq!(tracks[0].isrc.as_deref(), Some("GBAYE0601690"));
+    assert!(tracks[0].rems.iter().all(|r| r.name != "ISRCS"));
+    assert_eq!(tracks[1].isrc.as_deref(), Some("GBAYE0601691"));
+    assert!(tracks[1].rems.contains(&Rem::new("ISRCS", "GBAYE0601691 GBUM71505012")));
+    assert_eq!(tracks[2].isrc, None);
+}
+
+#[test]
+fn songwriters() {
+    let media = build(|b| b.credit_separator(" & "));
+    let tracks = media[0].cue_sheet.tracks().collect::<Vec<_>>();
+
+    assert_eq!(tracks[0].songwriter.as_deref(), Some("John Lennon & Paul McCartney"));
+    assert!(tracks[0].rems.contains(&Rem::quoted("COMPOSER", "John Lennon & Paul McCartney")));
+    assert!(tracks[0].rems.contains(&Rem::quoted("LYRICIST", "John Lennon")));
+    assert_eq!(tracks[1].songwriter.as_deref(), Some("George Harrison"));
+    assert_eq!(tracks[2].songwriter, None);
+}
+
+#[test]
+fn classical() {
+    let release: Release = serde_json::from_str(include_str!("fixtures/classical.json")).unwrap();
+    let media = CueSheetBuilder::new(&release).classical(true).build();
+    assert_eq!(media[0].cue_sheet.performer.as_deref(), Some("Ludwig van Beethoven"));
+
+    let tracks = media[0].cue_sheet.tracks().collect::<Vec<_>>();
+    assert_eq!(tracks[0].title.as_deref(), Some("Symphony no. 5 in C minor, op. 67: I. Allegro con brio"));
+    assert_eq!(
+        tracks[0].rems.iter().skip_while(|r| r.name != "WORK").cloned().collect::<Vec<_>>(),
+        [
+            Rem::quoted("WORK", "Symphony no. 5 in C minor, op. 67"),
+            Rem::quoted("MOVEMENTNAME", "Allegro con brio"),
+            Rem::new("MOVEMENT", "1"),
+            Rem::quoted("CONDUCTOR", "Herbert von Karajan"),
+            Rem::quoted("ORCHESTRA", "Berliner Philharmoniker"),
+        ]
+    );
+    assert!(tracks[1].rems.contains(&Rem::new("MOVEMENT", "2")));
+    assert!(tracks[1].rems.contains(&Rem::quoted("SOLOISTS", "Gidon Kremer (violin)")));
+
+    let media = CueSheetBuilder::new(&release).build();
+    assert_eq!(media[0].cue_sheet.performer.as_deref(), Some("Ludwig van Beethoven; Berliner Philharmoniker, Herbert von Karajan"));
+    assert!(media[0].cue_sheet.tracks().all(|t| t.rems.iter().all(|r| r.name != "WORK")));
+}
+
+#[test]
+fn musicbrainz_ids() {
+    let media = build(|b| b);
+    let rems = &media[0].cue_sheet.rems;
+    assert!(rems.contains(&Rem::new("MUSICBRAINZ_ALBUMARTISTID", "b10bbbfc-cf9e-42e0-be17-e2c3e1d2600d")));
+    assert!(rems.contains(&Rem::new("MUSICBRAINZ_RELEASEGROUPID", "9162580e-5df4-32de-80cc-f45a8d8a9b1d")));
+
+    let track = media[1].cue_sheet.tracks().next().unwrap();
+    assert!(track.rems.contains(&Rem::new("MUSICBRAINZ_TRACKID", "2d2ce2cc-fbd0-4fba-a3c6-1c8ab7a2c0f4")));
+    assert!(track.rems.contains(&Rem::new("MUSICBRAINZ_RELEASETRACKID", "7e5f2d5a-7b1b-3b4e-8e0a-1f3c8f0d2a04")));
+    assert!(track.rems.contains(&Rem::new("MUSICBRAINZ_ARTISTID", "b10bbbfc-cf9e-42e0-be17-e2c3e1d2600d")));
+}
+
+#[test]
+fn disc_numbering() {
+    let media = build(|b| b);
+    assert_eq!(media[1].cue_sheet.title.as_deref(), Some("Abbey Road- CD 02: Side Two"));
+    let rems = &media[1].cue_sheet.rems;
+    assert!(rems.contains(&Rem::new("DISCNUMBER", "2")));
+    assert!(rems.contains(&Rem::new("TOTALDISCS", "2")));
+    assert!(rems.contains(&Rem::new("TOTALTRACKS", "2")));
+    assert!(rems.contains(&Rem::quoted("DISCSUBTITLE", "Side Two")));
+
+    let media = build(|b| b.title_suffix(false));
+    assert_eq!(media[1].cue_sheet.title.as_deref(), Some("Abbey Road"));
+}
+
+#[test]
+fn dates() {
+    let rems = |date_source| build(|b| b.date_source(date_source)).remove(0).cue_sheet.rems;
+
+    let release_group = rems(DateSource::ReleaseGroup);
+    assert!(release_group.contains(&Rem::new("DATE", "1969-09-26")));
+    assert!(release_group.contains(&Rem::new("RELEASECOUNTRY", "GB")));
+    assert!(release_group.contains(&Rem::new("RELEASESTATUS", "official")));
+
+    let release = rems(DateSource::Release);
+    assert!(release.contains(&Rem::new("DATE", "1987-10-19")));
+
+    for rems in [release_group, release] {
+        assert!(rems.iter().all(|r| r.name != "ORIGINALDATE"));
+    }
+
+    let both = rems(DateSource::Both);
+    assert!(both.contains(&Rem::new("DATE", "1987-10-19")));
+    assert!(both.contains(&Rem::new("ORIGINALDATE", "1969-09-26")));
+
+    let release: Release = serde_json::from_str(include_str!("fixtures/classical.json")).unwrap();
+    let media = CueSheetBuilder::new(&release).date_source(DateSource::Release).build();
+    assert!(media[0].cue_sheet.rems.contains(&Rem::new("DATE", "1963")));
+}
+
+#[test]
+fn labels_and_comment() {
+    let rems = build(|b| b).remove(0).cue_sheet.rems;
+    assert!(rems.contains(&Rem::quoted("LABEL", "Apple Records")));
+    assert!(rems.contains(&Rem::quoted("CATALOGNUMBER", "CDP 7 46446 2")));
+    assert!(rems.iter().all(|r| r.name != "COMMENT"));
+
+    let rems = build(|b| b.comment("Ripped with EAC")).remove(0).cue_sheet.rems;
+    assert!(rems.contains(&Rem::quoted("COMMENT", "Ripped with EAC")));
+}
+
+#[test]
+fn genres() {
+    let rems = build(|b| b).remove(0).cue_sheet.rems;
+    assert!(rems.contains(&Rem::new("GENRE", "rock; pop rock")));
+
+    let options = GenreOptions {
+        source: GenreSource::Release,
+        ..Default::default()
+    };
+    let rems = build(|b| b.genres(options)).remove(0).cue_sheet.rems;
+    assert!(rems.iter().all(|r| r.name != "GENRE"));
+}
+
+#[test]
+fn artist_sort_names() {
+    let media = build(|b| b);
+    assert!(media[0].cue_sheet.rems.contains(&Rem::quoted("ALBUMARTISTSORT", "Beatles, The")));
+    let track = media[0].cue_sheet.tracks().next().unwrap();
+    assert!(track.rems.contains(&Rem::quoted("ARTISTSORT", "Beatles, The")));
+
+    let media = build(|b| b.artist_name(ArtistName::Sort));
+    assert_eq!(media[0].cue_sheet.performer.as_deref(), Some("Beatles, The"));
+}
+
+#[test]
+fn album_performer() {
+    let media = build(|b| b);
+    assert!(media[0].cue_sheet.tracks().all(|t| t.performer.as_deref() == Some("The Beatles")));
+    assert!(media[0].cue_sheet.rems.iter().all(|r| r.name != "COMPILATION"));
+
+    let media = build(|b| b.omit_album_performer(true));
+    assert!(media[0].cue_sheet.tracks().all(|t| t.performer.is_none()));
+
+    let mut json: serde_json::Value = serde_json::from_str(RELEASE).unwrap();
+    json["artist-credit"][0]["name"] = "Various Artists".into();
+    json["artist-credit"][0]["artist"]["id"] = VARIOUS_ARTISTS_ID.into();
+    let release: Release = serde_json::from_value(json).unwrap();
+    let media = CueSheetBuilder::new(&release).omit_album_performer(true).build();
+    assert!(media[0].cue_sheet.rems.contains(&Rem::new("COMPILATION", "1")));
+    assert!(media[0].cue_sheet.tracks().all(|t| t.performer.as_deref() == Some("The Beatles")));
+}
+
+#[test]
+fn track_artists() {
+    let media = build(|b| b);
+    let track = media[1].cue_sheet.tracks().nth(1).unwrap();
+    assert_eq!(track.performer.as_deref(), Some("Beatles feat. George Martin"));
+    assert!(track.rems.contains(&Rem::quoted("ARTISTSORT", "Beatles, The feat. Martin, George")));
+    let artist_ids = "b10bbbfc-cf9e-42e0-be17-e2c3e1d2600d; e2b2c3a4-0b6c-4d55-8a2e-5f1b7c9d3e61";
+    assert!(track.rems.contains(&Rem::new("MUSICBRAINZ_ARTISTID", artist_ids)));
+
+    let media = build(|b| b.recording_artists(true));
+    let track = media[1].cue_sheet.tracks().nth(1).unwrap();
+    assert_eq!(track.performer.as_deref(), Some("The Beatles"));
+    assert!(track.rems.contains(&Rem::new("MUSICBRAINZ_ARTISTID", "b10bbbfc-cf9e-42e0-be17-e2c3e1d2600d")));
+}
+
+#[test]
+fn latin_script() {
+    let release: Release = serde_json::from_str(include_str!("fixtures/japanese.json")).unwrap();
+    let pseudo_release: Release = serde_json::from_str(include_str!("fixtures/japanese_latin.json")).unwrap();
+
+    let media = CueSheetBuilder::new(&release).pseudo_release(&pseudo_release).build();
+    assert_eq!(media[0].cue_sheet.title.as_deref(), Some("ひこうき雲"));
+    assert!(media[0].cue_sheet.rems.iter().all(|r| r.name != "TITLE_LATIN"));
+
+    let media = CueSheetBuilder::new(&release).pseudo_release(&pseudo_release).script(Script::Latin).build();
+    assert_eq!(media[0].cue_sheet.title.as_deref(), Some("Hikōki-gumo"));
+    let tracks = media[0].cue_sheet.tracks().collect::<Vec<_>>();
+    assert_eq!(tracks[1].title.as_deref(), Some("Kumorizora"));
+    assert!(tracks[1].rems.contains(&Rem::new("MUSICBRAINZ_RELEASETRACKID", "5e6f7a8b-9c0d-4e1f-a2b3-c4d5e6f7a802")));
+
+    let media = CueSheetBuilder::new(&release).pseudo_release(&pseudo_release).script(Script::Both).build();
+    assert_eq!(media[0].cue_sheet.title.as_deref(), Some("ひこうき雲"));
+    assert!(media[0].cue_sheet.rems.contains(&Rem::quoted("TITLE_LATIN", "Hikōki-gumo")));
+    let tracks = media[0].cue_sheet.tracks().collect::<Vec<_>>();
+    assert_eq!(tracks[1].title.as_deref(), Some("曇り空"));
+    assert!(tracks[1].rems.contains(&Rem::quoted("TITLE_LATIN", "Kumorizora")));
+}
+
+#[test]
+fn indexes() {
+    let mut json: serde_json::Value = serde_json::from_str(RELEASE).unwrap();
+    json["media"][0]["discs"] = serde_json::json!([
+        { "id": "8r1Hj3YN5530tvSIZQym0GHiTrU-", "offset-count": 3, "sectors": 48915, "offsets": [150, 19663, 33390] }
+    ]);
+    json["media"][1]["tracks"][0]["length"] = serde_json::Value::Null;
+    let release: Release = serde_json::from_value(json).unwrap();
+    let media = CueSheetBuilder::new(&release).build();
+
+    let index_01 = |medium: &MediumCueSheet| medium.cue_sheet.tracks().map(|t| t.indexes.first().map(|i| i.time)).collect::<Vec<_>>();
+    let starts = [CueTime::from_msf(0, 0, 0), CueTime::from_msf(4, 20, 13), CueTime::from_msf(7, 23, 15)];
+    assert_eq!(index_01(&media[0]), starts.map(Some));
+    let rems = &media[0].cue_sheet.rems;
+    assert!(rems.contains(&Rem::new("INDEX_SOURCE", "TOC")));
+    assert!(rems.contains(&Rem::new("DISCID", "19028A03")));
+    assert!(rems.contains(&Rem::new("MUSICBRAINZ_DISC_ID", "8r1Hj3YN5530tvSIZQym0GHiTrU-")));
+
+    // the second track starts after the first one, whose length is not known
+    assert_eq!(index_01(&media[1]), [Some(CueTime::from_msf(0, 0, 0)), None]);
+    assert!(media[1].cue_sheet.rems.contains(&Rem::new("INDEX_SOURCE", "TRACK_LENGTHS")));
+    assert!(media[1].cue_sheet.rems.iter().all(|r| r.name != "DISCID"));
+}
+
+#[test]
+fn catalog_placeholder() {
+    let template: Template = "{album} [{catalog}]".parse().unwrap();
+    let media = build(|b| b.output_template(template.clone()));
+    assert_eq!(media[0].name, "Abbey Road [CDP 7 46446 2]");
+
+    let mut json: serde_json::Value = serde_json::from_str(RELEASE).unwrap();
+    json["label-info"][0]["catalog-number"] = "[none]".into();
+    let release: Release = serde_json::from_value(json).unwrap();
+    let media = CueSheetBuilder::new(&release).output_template(template).build();
+    assert_eq!(media[0].name, "Abbey Road []");
+    assert!(media[0].cue_sheet.rems.iter().all(|r| r.name != "CATALOGNUMBER"));
+}
+
+#[test]
+fn split_rip() {
+    let rip = parse(
+        "FILE \"CDImage.wav\" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 04:18:00
+    INDEX 01 04:20:13
+  TRACK 03 AUDIO
+    INDEX 01 07:23:15
+",
+    )
+    .unwrap();
+    let release = release();
+    let builder = CueSheetBuilder::new(&release).layout(Layout::TracksGapsAppended);
+    let mut merged = merge(&rip, &builder.build()[0].cue_sheet).unwrap();
+    builder.split_rip(&mut merged, 1).unwrap();
+
+    let names = merged.files.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["01 - Come Together.flac", "02 - Something.flac", "03 - Maxwell’s Silver Hammer.flac"]);
+    // the gap before the second track is at the end of the first file
+    let gap = &merged.files[0].tracks[1];
+    assert_eq!((gap.number, gap.indexes[0].number, gap.indexes[0].time), (2, 0, CueTime::from_msf(4, 18, 0)));
+    assert_eq!(merged.files[1].leading_indexes[0].time, CueTime::from_msf(0, 0, 0));
+
+    // the track layouts need a single FILE to split, while the image layout keeps the files as they are
+    assert!(builder.split_rip(&mut merged, 1).is_err());
+    let files = merged.files.clone();
+    CueSheetBuilder::new(&release).split_rip(&mut merged, 1).unwrap();
+    assert_eq!(merged.files, files);
+}
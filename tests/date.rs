@@ -0,0 +1,31 @@
+use chrono::NaiveDate;
+use musicbrainz_cuesheet::date::PartialDate;
+
+#[test]
+fn year() {
+    assert_eq!("1995".parse(), Ok(PartialDate::Year(1995)));
+    assert_eq!(PartialDate::Year(1995).to_string(), "1995");
+}
+
+#[test]
+fn year_month() {
+    assert_eq!("1995-03".parse(), Ok(PartialDate::YearMonth(1995, 3)));
+    assert_eq!(PartialDate::YearMonth(1995, 3).to_string(), "1995-03");
+}
+
+#[test]
+fn full_date() {
+    let date = NaiveDate::from_ymd_opt(1995, 1, 1).unwrap();
+    assert_eq!("1995-01-01".parse(), Ok(PartialDate::Date(date)));
+    assert_eq!(PartialDate::Date(date).to_string(), "1995-01-01");
+}
+
+#[test]
+fn invalid() {
+    assert!("".parse::<PartialDate>().is_err());
+    assert!("95".parse::<PartialDate>().is_err());
+    assert!("1995-13".parse::<PartialDate>().is_err());
+    assert!("1995-02-30".parse::<PartialDate>().is_err());
+    assert!("1995-3-1".parse::<PartialDate>().is_err());
+    assert!("????-03-01".parse::<PartialDate>().is_err());
+}
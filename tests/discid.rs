@@ -0,0 +1,30 @@
+use musicbrainz_cuesheet::{parse, CueTime, DiscIds, Toc};
+
+const TOC: &str = "1 6 242457 150 44942 61305 72755 96360 130485";
+
+#[test]
+fn ids_from_toc() {
+    let disc_ids = DiscIds::new(&TOC.parse().unwrap());
+    assert_eq!(disc_ids.musicbrainz, "7lAd6g1Tu2xcmnh41d6rlq1cTAs-");
+    assert_eq!(disc_ids.freedb_string(), "5C0C9E06");
+    assert_eq!(disc_ids.accuraterip_string(), "dBAR-006-0009e0ec-0035c077-5c0c9e06.bin");
+}
+
+#[test]
+fn toc_from_cue_sheet() {
+    let toc: Toc = TOC.parse().unwrap();
+    let mut cue = String::from("FILE \"CDImage.flac\" WAVE\n");
+    for (i, start) in toc.track_starts().iter().enumerate() {
+        cue += &format!("  TRACK {:02} AUDIO\n    INDEX 01 {start}\n", i + 1);
+    }
+
+    let length = CueTime::from_frames(toc.leadout - 150);
+    assert_eq!(Toc::from_cue_sheet(&parse(&cue).unwrap(), length).unwrap(), toc);
+}
+
+#[test]
+fn invalid_toc() {
+    assert!("1 6 242457 150 44942".parse::<Toc>().is_err());
+    assert!("1 2 242457 44942 150".parse::<Toc>().is_err());
+    assert!("1 1 100 150".parse::<Toc>().is_err());
+}
@@ -0,0 +1,62 @@
+use musicbrainz_cuesheet::encoding::{decode, transliterate_cue_sheet, TextEncoding};
+use musicbrainz_cuesheet::escape::{escape_cue_sheet, QuotePolicy};
+use musicbrainz_cuesheet::{parse, serialize_escaped};
+
+#[test]
+fn encode() {
+    assert_eq!(TextEncoding::Utf8Bom.encode("é"), b"\xEF\xBB\xBF\xC3\xA9");
+    assert_eq!(TextEncoding::Latin1.encode("Café"), b"Caf\xE9");
+    assert_eq!(TextEncoding::Windows1252.encode("“Ő”"), b"\x93O\x94");
+    assert_eq!(TextEncoding::ShiftJis.encode("東京"), b"\x93\x8C\x8B\x9E");
+}
+
+#[test]
+fn transliterate() {
+    assert_eq!(TextEncoding::Utf8.transliterate("Motörhead – 東京"), "Motörhead – 東京");
+    assert_eq!(TextEncoding::Latin1.transliterate("Motörhead – Őrült"), "Motörhead - Orült");
+    assert_eq!(TextEncoding::ShiftJis.transliterate("Björk"), "Bjork");
+    assert_eq!(TextEncoding::Latin1.transliterate("12” Single Mix"), "12' Single Mix");
+}
+
+#[test]
+fn lossy_fields() {
+    let mut cue_sheet = parse("PERFORMER \"Sigur Rós\"\nTITLE \"Ágætis byrjun\"\nREM COMMENT \"Þ – ð\"\n").unwrap();
+
+    let lossy = transliterate_cue_sheet(&mut cue_sheet, TextEncoding::Latin1).unwrap();
+    assert_eq!(lossy.len(), 1);
+    assert_eq!(lossy[0].field, "REM COMMENT");
+    assert_eq!(lossy[0].rewritten, "Þ - ð");
+    assert_eq!(cue_sheet.title.as_deref(), Some("Ágætis byrjun"));
+}
+
+#[test]
+fn escaped_latin1_fields() {
+    let mut cue_sheet = parse("FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n").unwrap();
+    cue_sheet.title = Some("Quote \u{201F}\u{3003}\u{FF02}".to_string());
+    cue_sheet.files[0].tracks[0].title = Some("12\" Single Mix".to_string());
+
+    escape_cue_sheet(&mut cue_sheet, QuotePolicy::Typographic);
+    transliterate_cue_sheet(&mut cue_sheet, TextEncoding::Latin1).unwrap();
+    let encoded = TextEncoding::Latin1.encode(&serialize_escaped(&cue_sheet, QuotePolicy::Typographic));
+
+    let reparsed = parse(&decode(&encoded)).unwrap();
+    assert_eq!(reparsed.files[0].tracks[0].title.as_deref(), Some("12' Single Mix"));
+    assert!(!reparsed.title.unwrap().contains('"'));
+}
+
+#[test]
+fn untransliterated_file_names() {
+    let mut cue_sheet = parse("FILE \"東京.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n").unwrap();
+
+    assert!(transliterate_cue_sheet(&mut cue_sheet, TextEncoding::Latin1).is_err());
+    assert_eq!(cue_sheet.files[0].name, "東京.wav");
+    assert!(transliterate_cue_sheet(&mut cue_sheet, TextEncoding::ShiftJis).unwrap().is_empty());
+}
+
+#[test]
+fn decode_input() {
+    assert_eq!(decode(b"TITLE \"Sigur R\xf3s\""), "TITLE \"Sigur Rós\"");
+    assert_eq!(decode("TITLE \"Sigur Rós\"".as_bytes()), "TITLE \"Sigur Rós\"");
+    assert_eq!(decode(b"\xEF\xBB\xBFTITLE \"\xC3\xB3\""), "TITLE \"ó\"");
+    assert_eq!(decode(b"\xFF\xFET\0I\0"), "TI");
+}
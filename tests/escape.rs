@@ -0,0 +1,40 @@
+use musicbrainz_cuesheet::escape::{escape_cue_sheet, escape_field, QuotePolicy};
+use musicbrainz_cuesheet::{parse, serialize, serialize_escaped};
+
+#[test]
+fn quote_policies() {
+    let title = "\"Weird Al\" - 12\" Single Mix";
+    assert_eq!(escape_field(title, QuotePolicy::Typographic), "“Weird Al” - 12” Single Mix");
+    assert_eq!(escape_field(title, QuotePolicy::Single), "'Weird Al' - 12' Single Mix");
+    assert_eq!(escape_field(title, QuotePolicy::Strip), "Weird Al - 12 Single Mix");
+}
+
+#[test]
+fn control_characters() {
+    assert_eq!(escape_field("Line one\r\nLine two\u{7}", QuotePolicy::Strip), "Line one Line two");
+    assert_eq!(escape_field("Plain", QuotePolicy::Strip), "Plain");
+}
+
+#[test]
+fn escaped_fields() {
+    let mut cue_sheet = parse("TITLE \"Album\"\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n").unwrap();
+    cue_sheet.files[0].tracks[0].title = Some("12\" Single Mix".to_string());
+
+    let rewritten = escape_cue_sheet(&mut cue_sheet, QuotePolicy::Typographic);
+    assert_eq!(rewritten.len(), 1);
+    assert_eq!(rewritten[0].field, "TRACK 01 TITLE");
+    assert_eq!(rewritten[0].original, "12\" Single Mix");
+
+    let reparsed = parse(&serialize(&cue_sheet)).unwrap();
+    assert_eq!(reparsed.files[0].tracks[0].title.as_deref(), Some("12” Single Mix"));
+}
+
+#[test]
+fn serialize_escaped_fields() {
+    let mut cue_sheet = parse("TITLE \"Album\"\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n").unwrap();
+    cue_sheet.files[0].tracks[0].title = Some("12\" Single Mix".to_string());
+
+    let reparsed = parse(&serialize_escaped(&cue_sheet, QuotePolicy::Single)).unwrap();
+    assert_eq!(reparsed.files[0].tracks[0].title.as_deref(), Some("12' Single Mix"));
+    assert_eq!(cue_sheet.files[0].tracks[0].title.as_deref(), Some("12\" Single Mix"));
+}
@@ -0,0 +1,31 @@
+﻿REM GENRE Rock
+REM DATE 1993
+REM DISCID 9A0B5E0C
+REM COMMENT "ExactAudioCopy v1.6"
+CATALOG 0720642470526
+PERFORMER "Nirvana"
+TITLE "In Utero"
+SONGWRITER "Kurt Cobain"
+CDTEXTFILE "In Utero.cdt"
+FILE "01 - Serve the Servants.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "Serve the Servants"
+    PERFORMER "Nirvana"
+    SONGWRITER "Kurt Cobain"
+    ISRC USGF19362901
+    FLAGS DCP PRE
+    PREGAP 00:00:32
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Scentless Apprentice"
+    PERFORMER "Nirvana"
+    REM REPLAYGAIN_TRACK_GAIN -8.21 dB
+    INDEX 00 03:34:10
+FILE "02 - Scentless Apprentice.wav" WAVE
+    INDEX 01 00:00:00
+  TRACK 03 AUDIO
+    TITLE "Heart-Shaped Box"
+    PERFORMER "Nirvana"
+    INDEX 00 03:46:20
+    INDEX 01 03:48:00
+    POSTGAP 00:02:00
@@ -0,0 +1,30 @@
+use musicbrainz_cuesheet::genre::{GenreMap, GenreOptions};
+
+fn candidates() -> Vec<(String, u32)> {
+    [("pop rock", 5), ("rock", 12), ("psychedelic pop", 1), ("Hip hop", 3)].into_iter().map(|(n, v)| (n.to_string(), v)).collect()
+}
+
+#[test]
+fn votes_and_limit() {
+    let options = GenreOptions::default();
+    assert_eq!(options.select_from(candidates()), ["rock", "pop rock", "Hip hop", "psychedelic pop"]);
+
+    let options = GenreOptions {
+        min_votes: 2,
+        limit: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(options.select_from(candidates()), ["rock", "pop rock"]);
+}
+
+#[test]
+fn map() {
+    let map: GenreMap = "# canonical genres\nRock\npop rock = Rock\nhip hop = Hip-Hop\n".parse().unwrap();
+    let options = GenreOptions {
+        map: Some(map),
+        ..Default::default()
+    };
+    assert_eq!(options.select_from(candidates()), ["Rock", "Hip-Hop"]);
+
+    assert!(" = Rock".parse::<GenreMap>().is_err());
+}
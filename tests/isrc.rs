@@ -0,0 +1,10 @@
+use musicbrainz_cuesheet::isrc::normalize_isrc;
+
+#[test]
+fn normalize() {
+    assert_eq!(normalize_isrc("USGF19362901").as_deref(), Some("USGF19362901"));
+    assert_eq!(normalize_isrc("gb-aye-69-00531").as_deref(), Some("GBAYE6900531"));
+    assert_eq!(normalize_isrc("1SGF19362901"), None);
+    assert_eq!(normalize_isrc("USGF1936290"), None);
+    assert_eq!(normalize_isrc("USGF193629O1"), None);
+}
@@ -0,0 +1,97 @@
+use musicbrainz_cuesheet::layout::split_image;
+use musicbrainz_cuesheet::{parse, serialize, CueFile, Layout};
+
+const IMAGE: &str = "FILE \"CDImage.flac\" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 03:00:00
+    INDEX 01 03:02:00
+  TRACK 03 AUDIO
+    INDEX 01 05:00:00
+";
+
+fn image() -> CueFile {
+    parse(IMAGE).unwrap().files.remove(0)
+}
+
+fn split(layout: Layout) -> String {
+    let mut cue_sheet = parse(IMAGE).unwrap();
+    cue_sheet.files = split_image(image(), layout, |t| format!("{:02}.flac", t.number)).unwrap();
+    serialize(&cue_sheet)
+}
+
+#[test]
+fn image_layout() {
+    assert_eq!(split(Layout::Image), IMAGE);
+}
+
+#[test]
+fn tracks() {
+    assert_eq!(
+        split(Layout::Tracks),
+        "FILE \"01.flac\" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+FILE \"02.flac\" WAVE
+  TRACK 02 AUDIO
+    INDEX 01 00:00:00
+FILE \"03.flac\" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+"
+    );
+}
+
+#[test]
+fn tracks_gaps_appended() {
+    assert_eq!(
+        split(Layout::TracksGapsAppended),
+        "FILE \"01.flac\" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 03:00:00
+FILE \"02.flac\" WAVE
+    INDEX 01 00:00:00
+FILE \"03.flac\" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+"
+    );
+}
+
+#[test]
+fn tracks_gaps_prepended() {
+    assert_eq!(
+        split(Layout::TracksGapsPrepended),
+        "FILE \"01.flac\" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+FILE \"02.flac\" WAVE
+  TRACK 02 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:02:00
+FILE \"03.flac\" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+"
+    );
+}
+
+#[test]
+fn out_of_order_indexes() {
+    let image = parse(
+        "FILE \"CDImage.flac\" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 02:00:00
+  TRACK 02 AUDIO
+    INDEX 00 01:00:00
+    INDEX 01 03:00:00
+",
+    )
+    .unwrap()
+    .files
+    .remove(0);
+    assert!(split_image(image, Layout::TracksGapsAppended, |t| format!("{:02}.flac", t.number)).is_err());
+}
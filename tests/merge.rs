@@ -0,0 +1,58 @@
+use musicbrainz_cuesheet::merge::merge_release;
+use musicbrainz_cuesheet::{merge, parse, CueSheet, CueSheetBuilder, Layout, MergeError, Rem};
+use musicbrainz_rs::entity::release::Release;
+
+const EAC: &str = include_str!("fixtures/eac.cue");
+
+fn generated() -> CueSheet {
+    let release: Release = serde_json::from_str(include_str!("fixtures/release.json")).unwrap();
+    CueSheetBuilder::new(&release).build().remove(0).cue_sheet
+}
+
+#[test]
+fn keeps_rip_layout() {
+    let rip = parse(EAC).unwrap();
+    let generated = generated();
+    let merged = merge(&rip, &generated).unwrap();
+
+    assert_eq!(merged.title, generated.title);
+    assert_eq!(merged.catalog, rip.catalog);
+    assert_eq!(merged.rems.iter().filter(|r| r.name == "DISCID").count(), 1);
+    assert!(merged.rems.iter().all(|r| r.name != "INDEX_SOURCE"));
+    assert_eq!(merged.rems.iter().filter(|r| r.name == "GENRE").collect::<Vec<_>>(), vec![&Rem::new("GENRE", "rock; pop rock")]);
+
+    for (i, (merged_file, rip_file)) in merged.files.iter().zip(&rip.files).enumerate() {
+        assert_eq!(merged_file.name, rip_file.name, "file {i}");
+        assert_eq!(merged_file.leading_indexes, rip_file.leading_indexes, "file {i}");
+    }
+    for ((merged_track, rip_track), generated_track) in merged.tracks().zip(rip.tracks()).zip(generated.tracks()) {
+        assert_eq!(merged_track.title, generated_track.title);
+        assert_eq!(merged_track.indexes, rip_track.indexes);
+        assert_eq!(merged_track.pregap, rip_track.pregap);
+        assert_eq!(merged_track.flags, rip_track.flags);
+        assert_eq!(merged_track.isrc, rip_track.isrc.clone().or(generated_track.isrc.clone()));
+    }
+}
+
+#[test]
+fn refuses_different_track_count() {
+    let mut rip = parse(EAC).unwrap();
+    rip.files[1].tracks.clear();
+    let generated = generated();
+
+    assert_eq!(merge(&rip, &generated), Err(MergeError::TrackCountMismatch { rip: 2, release: 3 }));
+}
+
+#[test]
+fn merges_release() {
+    let rip = parse(EAC).unwrap();
+    let release: Release = serde_json::from_str(include_str!("fixtures/release.json")).unwrap();
+
+    // the default image layout keeps the files of a rip split by track
+    let merged = merge_release(&CueSheetBuilder::new(&release), &rip, None).unwrap();
+    assert_eq!(merged.files.iter().map(|f| &f.name).collect::<Vec<_>>(), rip.files.iter().map(|f| &f.name).collect::<Vec<_>>());
+    assert_eq!(merged.title.as_deref(), Some("Abbey Road- CD 01: Side One"));
+
+    let builder = CueSheetBuilder::new(&release).layout(Layout::Tracks);
+    assert!(matches!(merge_release(&builder, &rip, None), Err(MergeError::Split(_))));
+}
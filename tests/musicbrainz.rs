@@ -0,0 +1,36 @@
+use musicbrainz_cuesheet::musicbrainz::{discid_matches, toc_matches};
+use musicbrainz_cuesheet::Toc;
+use musicbrainz_rs::entity::BrowseResult;
+use musicbrainz_rs::entity::release::Release;
+
+fn releases() -> Vec<Release> {
+    let result: BrowseResult<Release> = serde_json::from_str(include_str!("fixtures/toc_lookup.json")).unwrap();
+    result.entities
+}
+
+#[test]
+fn toc_lookup() {
+    let toc: Toc = "1 3 48915 150 19663 33390".parse().unwrap();
+    let matches = toc_matches(&releases(), &toc);
+
+    let media = matches.iter().map(|m| (m.release_id.as_str(), m.medium_position)).collect::<Vec<_>>();
+    assert_eq!(
+        media,
+        [
+            ("1a2b3c4d-0000-4000-8000-000000000002", 2),
+            ("1a2b3c4d-0000-4000-8000-000000000001", 1),
+            ("1a2b3c4d-0000-4000-8000-000000000003", 1),
+        ]
+    );
+    assert!(matches.iter().all(|m| m.toc == toc));
+}
+
+#[test]
+fn discid_lookup() {
+    let matches = discid_matches(&releases(), "Xq5m1Bo3DnX7jyMTLtIl4ZKvnUc-");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].release_id, "1a2b3c4d-0000-4000-8000-000000000001");
+    assert_eq!(matches[0].toc.to_string(), "1 3 48900 150 19650 33400");
+
+    assert!(discid_matches(&releases(), "unknown").is_empty());
+}
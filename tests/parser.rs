@@ -0,0 +1,75 @@
+use musicbrainz_cuesheet::{parse, serialize, CueIndex, CueSheetBuilder, CueTime, FileType, Rem};
+use musicbrainz_rs::entity::release::Release;
+
+const EAC: &str = include_str!("fixtures/eac.cue");
+
+/// The cuesheet the generator writes for the first medium of the release fixture.
+fn generated() -> String {
+    let release: Release = serde_json::from_str(include_str!("fixtures/release.json")).unwrap();
+    serialize(&CueSheetBuilder::new(&release).build()[0].cue_sheet)
+}
+
+#[test]
+fn generated_round_trip() {
+    let generated = generated();
+    let cue_sheet = parse(&generated).unwrap();
+    assert_eq!(serialize(&cue_sheet), generated);
+}
+
+#[test]
+fn generated_model() {
+    let cue_sheet = parse(&generated()).unwrap();
+    assert_eq!(cue_sheet.title.as_deref(), Some("Abbey Road- CD 01: Side One"));
+    assert_eq!(cue_sheet.performer.as_deref(), Some("The Beatles"));
+    assert_eq!(cue_sheet.rems[0], Rem::new("GENRE", "rock; pop rock"));
+    assert!(cue_sheet.rems.contains(&Rem::quoted("LABEL", "Apple Records")));
+    assert!(cue_sheet.rems.contains(&Rem::quoted("DISCSUBTITLE", "Side One")));
+    assert_eq!(cue_sheet.files.len(), 1);
+    assert_eq!(cue_sheet.files[0].name, "CDImage.flac");
+    assert_eq!(cue_sheet.files[0].file_type, FileType::Wave);
+
+    let tracks = cue_sheet.tracks().collect::<Vec<_>>();
+    assert_eq!(tracks.len(), 3);
+    assert_eq!(tracks[2].title.as_deref(), Some("Maxwell’s Silver Hammer"));
+    assert_eq!(tracks[1].indexes, vec![CueIndex { number: 1, time: CueTime::from_msf(4, 20, 13) }]);
+}
+
+#[test]
+fn eac_model() {
+    let cue_sheet = parse(EAC).unwrap();
+    assert_eq!(cue_sheet.catalog.as_deref(), Some("0720642470526"));
+    assert_eq!(cue_sheet.cdtextfile.as_deref(), Some("In Utero.cdt"));
+    assert_eq!(cue_sheet.songwriter.as_deref(), Some("Kurt Cobain"));
+    assert_eq!(cue_sheet.rems.len(), 4);
+    assert_eq!(cue_sheet.rems[3], Rem::quoted("COMMENT", "ExactAudioCopy v1.6"));
+
+    let first = &cue_sheet.files[0].tracks[0];
+    assert_eq!(first.isrc.as_deref(), Some("USGF19362901"));
+    assert_eq!(first.flags, vec!["DCP", "PRE"]);
+    assert_eq!(first.pregap, Some(CueTime::from_frames(32)));
+
+    let second = &cue_sheet.files[0].tracks[1];
+    assert_eq!(second.rems, vec![Rem::new("REPLAYGAIN_TRACK_GAIN", "-8.21 dB")]);
+    assert_eq!(second.indexes, vec![CueIndex { number: 0, time: CueTime::from_msf(3, 34, 10) }]);
+    assert_eq!(cue_sheet.files[1].leading_indexes, vec![CueIndex { number: 1, time: CueTime::default() }]);
+
+    let third = &cue_sheet.files[1].tracks[0];
+    assert_eq!(third.number, 3);
+    assert_eq!(third.indexes.len(), 2);
+    assert_eq!(third.postgap, Some(CueTime::from_msf(0, 2, 0)));
+}
+
+#[test]
+fn eac_round_trip() {
+    let cue_sheet = parse(EAC).unwrap();
+    assert_eq!(parse(&serialize(&cue_sheet)).unwrap(), cue_sheet);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(parse("TITLE \"x\"\n  TRACK 01 AUDIO\n").unwrap_err().line, 2);
+    assert!(parse("FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:60\n").is_err());
+    assert!(parse("FILE \"a.wav\" WAVE\n    INDEX 01 00:00:00\n").is_err());
+    assert!(parse("FOO bar\n").is_err());
+    assert_eq!(parse("FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 99999999:00:00\n").unwrap_err().line, 3);
+}
@@ -0,0 +1,24 @@
+use musicbrainz_cuesheet::pseudo_release::{is_latin, translations, Script};
+use musicbrainz_rs::entity::release::Release;
+
+#[test]
+fn parse() {
+    assert_eq!("original".parse(), Ok(Script::Original));
+    assert_eq!("both".parse(), Ok(Script::Both));
+    assert!("romaji".parse::<Script>().is_err());
+}
+
+#[test]
+fn latin_translation() {
+    let release: Release = serde_json::from_str(include_str!("fixtures/japanese.json")).unwrap();
+    assert_eq!(is_latin(&release), Some(false));
+
+    let pseudo_releases = translations(&release).collect::<Vec<_>>();
+    assert_eq!(pseudo_releases.len(), 1);
+    assert_eq!(pseudo_releases[0].id, "7d8e9f0a-1b2c-4d3e-8f4a-5b6c7d8e9f0a");
+    assert_eq!(is_latin(pseudo_releases[0]), Some(true));
+
+    let release: Release = serde_json::from_str(include_str!("fixtures/release.json")).unwrap();
+    assert_eq!(translations(&release).count(), 0);
+    assert_eq!(is_latin(&release), None);
+}
@@ -0,0 +1,31 @@
+use musicbrainz_cuesheet::builder::check_name_collisions;
+use musicbrainz_cuesheet::sanitize::sanitize_file_name;
+use musicbrainz_cuesheet::{CueSheet, MediumCueSheet};
+
+#[test]
+fn reserved_characters() {
+    assert_eq!(sanitize_file_name("AC/DC - Back in Black"), "AC_DC - Back in Black");
+    assert_eq!(sanitize_file_name("What? <Live>: \"1999\" *|\\"), "What_ _Live__ _1999_ ___");
+    assert_eq!(sanitize_file_name("Tab\tand\nnewline"), "Tab_and_newline");
+    assert_eq!(sanitize_file_name("Ends with dots... "), "Ends with dots");
+}
+
+#[test]
+fn reserved_names() {
+    assert_eq!(sanitize_file_name("CON"), "_CON");
+    assert_eq!(sanitize_file_name("com1.cue"), "_com1.cue");
+    assert_eq!(sanitize_file_name("Console"), "Console");
+    assert_eq!(sanitize_file_name("..."), "_");
+}
+
+#[test]
+fn name_collisions() {
+    let medium = |name: &str, position| MediumCueSheet {
+        name: name.to_string(),
+        position,
+        cue_sheet: CueSheet::default(),
+    };
+
+    assert!(check_name_collisions(&[medium("CD 01", 1), medium("CD 02", 2)]).is_ok());
+    assert!(check_name_collisions(&[medium("Album", 1), medium("album", 2)]).is_err());
+}
@@ -0,0 +1,25 @@
+use musicbrainz_cuesheet::template::TemplateValue;
+use musicbrainz_cuesheet::Template;
+
+#[test]
+fn render() {
+    let template: Template = "{artist} - {album} ({medium:02}/{discs}) {{{catalog}}}.flac".parse().unwrap();
+    let values = [
+        ("artist", TemplateValue::from("Nirvana")),
+        ("album", "In Utero".into()),
+        ("medium", 1.into()),
+        ("discs", 2.into()),
+    ];
+
+    assert_eq!(template.render(&values), "Nirvana - In Utero (01/2) {}.flac");
+    assert!(template.validate(&["artist", "album", "medium", "discs", "catalog"]).is_ok());
+    assert!(template.validate(&["artist", "album"]).is_err());
+}
+
+#[test]
+fn invalid() {
+    assert!("{artist".parse::<Template>().is_err());
+    assert!("artist}".parse::<Template>().is_err());
+    assert!("{}".parse::<Template>().is_err());
+    assert!("{medium:x}".parse::<Template>().is_err());
+}